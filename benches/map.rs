@@ -0,0 +1,81 @@
+// Recherches et génération sur une grande carte : cargo bench --bench map
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use ereea::game::game_automation::{distance_field, find_path};
+use ereea::map::cell::Cell;
+use ereea::map::flow::FlowTarget;
+use ereea::{Config, Map, Simulation};
+
+const SIZE: usize = 1000;
+const SEED: u32 = 42;
+
+// Case franchissable la plus éloignée de la station, pour un chemin qui traverse la carte
+fn far_corner(map: &Map) -> (usize, usize) {
+  let dist = distance_field(map, &[(9, 4)], true);
+  dist
+    .iter()
+    .filter_map(|(pos, d)| d.map(|d| (pos, d)))
+    .max_by_key(|&(_, d)| d)
+    .map_or((9, 4), |(pos, _)| pos)
+}
+
+fn bench_map(c: &mut Criterion) {
+  c.bench_function("generate 1000x1000", |b| {
+    b.iter(|| Map::try_new(black_box(SIZE), black_box(SIZE), SEED))
+  });
+
+  let mut map = Map::try_new(SIZE, SIZE, SEED).expect("carte");
+  let target = far_corner(&map);
+
+  c.bench_function("find_path across 1000x1000", |b| {
+    b.iter(|| find_path(9, 4, target.0, target.1, black_box(&map), true))
+  });
+  c.bench_function("distance_field 1000x1000", |b| {
+    b.iter(|| distance_field(black_box(&map), &[(9, 4)], true))
+  });
+
+  c.bench_function("sync_flow_fields 1000x1000", |b| {
+    b.iter(|| {
+      // Change la révélation à chaque fois pour forcer une reconstruction complète
+      map.sync_flow_fields(&[(9, 4)], false);
+      map.sync_flow_fields(&[(9, 4)], true);
+    })
+  });
+  c.bench_function("flow_hop science 1000x1000", |b| {
+    b.iter(|| black_box(&map).flow_hop(FlowTarget::Science, target.0, target.1))
+  });
+  // Collecte d'un minerai puis remise en place : deux mises à jour incrémentales
+  let mineral = map
+    .grid()
+    .iter()
+    .find(|&(_, &cell)| cell == Cell::Mineral)
+    .map(|(pos, _)| pos)
+    .expect("minerai");
+  c.bench_function("set_cell mineral 1000x1000", |b| {
+    b.iter(|| {
+      map.set_cell(mineral.0, mineral.1, Cell::Empty);
+      map.set_cell(mineral.0, mineral.1, Cell::Mineral);
+    })
+  });
+
+  let config = Config {
+    width: SIZE,
+    height: SIZE,
+    seed: SEED,
+    ..Config::default()
+  };
+  // Premier tour d'une partie neuve, construction des champs de distances comprise
+  c.bench_function("simulation step 1000x1000", |b| {
+    b.iter_batched(
+      || Simulation::new(&config).expect("simulation"),
+      |mut sim| sim.step(),
+      BatchSize::LargeInput,
+    )
+  });
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default().sample_size(10);
+  targets = bench_map
+}
+criterion_main!(benches);
@@ -0,0 +1,351 @@
+// Compare les stratégies d'automatisation sur une plage de graines et de tailles de carte.
+// Exemple : tournament --seeds 0..50 --ticks 2000 --size 50x15 --size 80x25 \
+//             --strategy default --script collector=scripts/collector.rhai
+use ereea::game::behaviour::{BehaviourRegistry, STRATEGIES};
+use ereea::game::command::parse_robot_type;
+use ereea::game::game_state::MIN_MAP_SIZE;
+use ereea::robot::robot::RobotType;
+use ereea::utils::locale::{Lang, Msg, set_lang};
+use ereea::{Config, GameError, Simulation};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// Une stratégie en lice : une flotte prédéfinie, ou la flotte par défaut dont un type est scripté
+enum Contender {
+  Builtin(String),
+  Script(RobotType, PathBuf),
+}
+
+impl Contender {
+  fn name(&self) -> String {
+    match self {
+      Contender::Builtin(name) => name.clone(),
+      Contender::Script(robot_type, path) => {
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        format!("{file} ({})", Msg::RobotKind(*robot_type))
+      }
+    }
+  }
+
+  fn setup(&self, sim: &mut Simulation) -> Result<(), GameError> {
+    match self {
+      Contender::Builtin(name) => {
+        sim.state.behaviours = BehaviourRegistry::named(name)
+          .ok_or_else(|| GameError::Config(Msg::UnknownStrategy(name).to_string()))?;
+        Ok(())
+      }
+      Contender::Script(robot_type, path) => sim.load_scripts(&[(*robot_type, path.clone())]),
+    }
+  }
+}
+
+struct Options {
+  seeds: Range<u32>,
+  ticks: u64,
+  sizes: Vec<(usize, usize)>,
+  threads: usize,
+  contenders: Vec<Contender>,
+}
+
+fn value(arg: Option<String>, option: &str, expected: &str) -> Result<String, String> {
+  arg.ok_or_else(|| Msg::MissingOptionValue(option, expected).to_string())
+}
+
+fn parse_number<T: std::str::FromStr>(word: &str, option: &str) -> Result<T, String> {
+  word
+    .parse()
+    .map_err(|_| Msg::MissingOptionValue(option, "N").to_string())
+}
+
+impl Options {
+  fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+    let default = Config::default();
+    let mut options = Options {
+      seeds: 0..20,
+      ticks: 1000,
+      sizes: Vec::new(),
+      threads: thread::available_parallelism().map_or(1, |n| n.get()),
+      contenders: Vec::new(),
+    };
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "--seeds" => {
+          let range = value(args.next(), "--seeds", "start..end")?;
+          let bad = || Msg::BadSeedRange(&range).to_string();
+          let (start, end) = range.split_once("..").ok_or_else(bad)?;
+          options.seeds = start.parse().map_err(|_| bad())?..end.parse().map_err(|_| bad())?;
+          if options.seeds.is_empty() {
+            return Err(bad());
+          }
+        }
+        "--ticks" => options.ticks = parse_number(&value(args.next(), "--ticks", "N")?, "--ticks")?,
+        "--threads" => {
+          options.threads =
+            parse_number::<usize>(&value(args.next(), "--threads", "N")?, "--threads")?.max(1)
+        }
+        "--size" => {
+          let size = value(args.next(), "--size", "WIDTHxHEIGHT")?;
+          let bad = || Msg::BadMapSize(&size).to_string();
+          let (width, height) = size.split_once('x').ok_or_else(bad)?;
+          let (width, height) = (
+            width.parse().map_err(|_| bad())?,
+            height.parse().map_err(|_| bad())?,
+          );
+          if width < MIN_MAP_SIZE.0 || height < MIN_MAP_SIZE.1 {
+            return Err(bad());
+          }
+          options.sizes.push((width, height));
+        }
+        "--strategy" => {
+          let name = value(args.next(), "--strategy", &STRATEGIES.join(" | "))?;
+          if !STRATEGIES.contains(&name.as_str()) {
+            return Err(Msg::UnknownStrategy(&name).to_string());
+          }
+          options.contenders.push(Contender::Builtin(name));
+        }
+        "--script" => {
+          let spec = value(args.next(), "--script", "<type>=<file.rhai>")?;
+          let Some((kind, path)) = spec.split_once('=') else {
+            return Err(Msg::ScriptOption(&spec).to_string());
+          };
+          options.contenders.push(Contender::Script(
+            parse_robot_type(kind).map_err(|e| e.to_string())?,
+            PathBuf::from(path),
+          ));
+        }
+        "--lang" => {
+          let name = value(args.next(), "--lang", "fr | en")?;
+          set_lang(Lang::parse(&name).ok_or_else(|| Msg::UnknownLang(&name).to_string())?);
+        }
+        _ => return Err(Msg::UnknownOption(&arg).to_string()),
+      }
+    }
+
+    if options.sizes.is_empty() {
+      options.sizes.push((default.width, default.height));
+    }
+    if options.contenders.is_empty() {
+      options.contenders = STRATEGIES
+        .iter()
+        .map(|name| Contender::Builtin(name.to_string()))
+        .collect();
+    }
+    Ok(options)
+  }
+}
+
+// Mesures d'une partie
+struct Outcome {
+  resources_per_tick: f64,
+  unlock_tick: Option<u64>,
+  idle_ratio: f64,
+}
+
+fn play(contender: &Contender, config: &Config, ticks: u64) -> Result<Outcome, GameError> {
+  let mut sim = Simulation::new(config)?;
+  contender.setup(&mut sim)?;
+
+  let mut unlock_tick = None;
+  for tick in 1..=ticks {
+    sim.step();
+    sim.events();
+    let (unlocked, total, _) = sim.state.map.zone_stats();
+    if unlock_tick.is_none() && unlocked == total {
+      unlock_tick = Some(tick);
+    }
+  }
+
+  let robots = &sim.state.robots;
+  let idle: u64 = robots.iter().map(|r| r.telemetry.ticks_idle).sum();
+  Ok(Outcome {
+    resources_per_tick: sim.state.stats.total_deliveries as f64 / ticks as f64,
+    unlock_tick,
+    idle_ratio: idle as f64 / (robots.len() as u64 * ticks) as f64,
+  })
+}
+
+// Ligne du classement
+struct Row {
+  name: String,
+  resources: Option<(f64, f64)>,
+  unlock: String,
+  idle: Option<(f64, f64)>,
+}
+
+// Quantile à 97,5 % de la loi de Student, pour un intervalle de confiance à 95 %
+fn student_95(degrees: usize) -> f64 {
+  const TABLE: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+  ];
+  TABLE.get(degrees.wrapping_sub(1)).copied().unwrap_or(1.96)
+}
+
+// Moyenne ± demi-largeur de l'intervalle de confiance à 95 %
+fn summarize(values: &[f64]) -> Option<(f64, f64)> {
+  let n = values.len();
+  if n == 0 {
+    return None;
+  }
+  let mean = values.iter().sum::<f64>() / n as f64;
+  if n == 1 {
+    return Some((mean, 0.0));
+  }
+  let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+  Some((mean, student_95(n - 1) * (variance / n as f64).sqrt()))
+}
+
+fn interval(summary: Option<(f64, f64)>, precision: usize) -> String {
+  match summary {
+    Some((mean, margin)) => format!("{mean:.precision$} ± {margin:.precision$}"),
+    None => "—".to_string(),
+  }
+}
+
+fn run() -> Result<(), GameError> {
+  set_lang(Lang::from_env());
+  let options = Options::from_args(std::env::args().skip(1)).map_err(GameError::Config)?;
+
+  // Une partie par stratégie, taille de carte et graine
+  let jobs: Vec<(usize, usize, u32)> = (0..options.sizes.len())
+    .flat_map(|size| {
+      let seeds = options.seeds.clone();
+      (0..options.contenders.len())
+        .flat_map(move |contender| seeds.clone().map(move |seed| (size, contender, seed)))
+    })
+    .collect();
+
+  let threads = options.threads.min(jobs.len());
+  println!(
+    "{}",
+    Msg::TournamentRuns {
+      runs: jobs.len(),
+      threads
+    }
+  );
+
+  // Chaque fil prend la prochaine partie libre jusqu'à épuisement
+  let next = AtomicUsize::new(0);
+  let results: Mutex<Vec<Option<Result<Outcome, GameError>>>> =
+    Mutex::new((0..jobs.len()).map(|_| None).collect());
+  thread::scope(|scope| {
+    for _ in 0..threads {
+      scope.spawn(|| {
+        loop {
+          let i = next.fetch_add(1, Ordering::Relaxed);
+          let Some(&(size, contender, seed)) = jobs.get(i) else {
+            break;
+          };
+          let (width, height) = options.sizes[size];
+          let config = Config {
+            width,
+            height,
+            seed,
+            ..Config::default()
+          };
+          let outcome = play(&options.contenders[contender], &config, options.ticks);
+          results.lock().unwrap()[i] = Some(outcome);
+        }
+      });
+    }
+  });
+
+  let mut outcomes: Vec<Vec<Vec<Outcome>>> = options
+    .sizes
+    .iter()
+    .map(|_| options.contenders.iter().map(|_| Vec::new()).collect())
+    .collect();
+  for (&(size, contender, _), result) in jobs.iter().zip(results.into_inner().unwrap()) {
+    if let Some(result) = result {
+      outcomes[size][contender].push(result?);
+    }
+  }
+
+  let runs = options.seeds.len();
+  for (&(width, height), by_contender) in options.sizes.iter().zip(&outcomes) {
+    println!();
+    println!(
+      "{}",
+      Msg::TournamentConfig {
+        width,
+        height,
+        ticks: options.ticks
+      }
+    );
+    println!("{}", Msg::TournamentColumns);
+
+    let mut rows: Vec<Row> = options
+      .contenders
+      .iter()
+      .zip(by_contender)
+      .map(|(contender, outcomes)| {
+        let collect =
+          |f: fn(&Outcome) -> Option<f64>| -> Vec<f64> { outcomes.iter().filter_map(f).collect() };
+        let unlocks = collect(|o| o.unlock_tick.map(|t| t as f64));
+        Row {
+          name: contender.name(),
+          resources: summarize(&collect(|o| Some(o.resources_per_tick))),
+          // Les parties où la carte n'a jamais été entièrement ouverte sont comptées à part
+          unlock: format!(
+            "{} ({}/{runs})",
+            interval(summarize(&unlocks), 0),
+            unlocks.len()
+          ),
+          idle: summarize(&collect(|o| Some(o.idle_ratio * 100.0))),
+        }
+      })
+      .collect();
+
+    // Classement par ressources livrées par tour
+    let mean = |row: &Row| row.resources.map_or(f64::NEG_INFINITY, |(m, _)| m);
+    rows.sort_by(|a, b| mean(b).total_cmp(&mean(a)));
+    for (rank, row) in rows.iter().enumerate() {
+      println!(
+        "{:<3} {:<28} {:>17} {:>26} {:>15}",
+        rank + 1,
+        row.name,
+        interval(row.resources, 3),
+        row.unlock,
+        interval(row.idle, 1)
+      );
+    }
+  }
+
+  Ok(())
+}
+
+fn main() -> ExitCode {
+  match run() {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("{e}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn summaries_give_the_mean_and_a_student_interval() {
+    assert_eq!(summarize(&[]), None);
+    assert_eq!(summarize(&[4.0]), Some((4.0, 0.0)));
+
+    let (mean, margin) = summarize(&[1.0, 2.0, 3.0]).unwrap();
+    assert_eq!(mean, 2.0);
+    // Écart-type 1 sur 3 valeurs, 2 degrés de liberté
+    assert!((margin - 4.303 / 3f64.sqrt()).abs() < 1e-9);
+
+    let (_, constant) = summarize(&[5.0; 10]).unwrap();
+    assert_eq!(constant, 0.0);
+  }
+}
@@ -1,7 +1,23 @@
+use crate::error::GameError;
+use crate::game::command::parse_robot_type;
+use crate::robot::robot::RobotType;
+use crate::utils::locale::{Lang, Msg};
+use crate::utils::theme::{ColorTheme, GlyphSet, Theme};
+use std::path::PathBuf;
+use std::str::FromStr;
+
 pub struct Config {
   pub width: usize,
   pub height: usize,
   pub seed: u32,
+  pub theme: Theme,
+  pub lang: Lang,
+  // Stratégies Rhai remplaçant celles par défaut, par type de robot
+  pub scripts: Vec<(RobotType, PathBuf)>,
+  // Fichier où sauvegarder la partie si elle est interrompue (Ctrl+C, SIGTERM)
+  pub autosave: Option<PathBuf>,
+  // Vérifie les invariants de la partie après chaque tour, au prix d'un parcours de la carte
+  pub check_invariants: bool,
 }
 
 impl Default for Config {
@@ -10,6 +26,91 @@ impl Default for Config {
       width: 50,
       height: 15,
       seed: 42,
+      theme: Theme::default(),
+      lang: Lang::from_env(),
+      scripts: Vec::new(),
+      autosave: None,
+      check_invariants: false,
     }
   }
 }
+
+fn value(arg: Option<String>, option: &str, expected: &str) -> Result<String, String> {
+  arg.ok_or_else(|| Msg::MissingOptionValue(option, expected).to_string())
+}
+
+fn number<T: FromStr>(arg: Option<String>, option: &str) -> Result<T, String> {
+  value(arg, option, "N")?
+    .parse()
+    .map_err(|_| Msg::MissingOptionValue(option, "N").to_string())
+}
+
+impl Config {
+  // Options : --width <N>, --height <N>, --seed <N>, --ascii, --glyphs <emoji|ascii>, --theme <classic|colorblind>, --lang <fr|en>,
+  // --script <type>=<fichier.rhai>, --autosave <fichier>, --check-invariants
+  pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, GameError> {
+    Self::parse(args).map_err(GameError::Config)
+  }
+
+  fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+    let mut config = Config::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "--width" => config.width = number(args.next(), "--width")?,
+        "--height" => config.height = number(args.next(), "--height")?,
+        "--seed" => config.seed = number(args.next(), "--seed")?,
+        "--ascii" => config.theme.glyphs = GlyphSet::Ascii,
+        "--glyphs" => {
+          let name = value(args.next(), "--glyphs", "emoji | ascii")?;
+          config.theme.glyphs =
+            GlyphSet::parse(&name).ok_or_else(|| Msg::UnknownGlyphSet(&name).to_string())?;
+        }
+        "--theme" => {
+          let name = value(args.next(), "--theme", "classic | colorblind")?;
+          config.theme.colors =
+            ColorTheme::parse(&name).ok_or_else(|| Msg::UnknownTheme(&name).to_string())?;
+        }
+        "--lang" => {
+          let name = value(args.next(), "--lang", "fr | en")?;
+          config.lang = Lang::parse(&name).ok_or_else(|| Msg::UnknownLang(&name).to_string())?;
+        }
+        "--script" => {
+          let spec = value(args.next(), "--script", "<type>=<file.rhai>")?;
+          let Some((kind, path)) = spec.split_once('=') else {
+            return Err(Msg::ScriptOption(&spec).to_string());
+          };
+          config.scripts.push((
+            parse_robot_type(kind).map_err(|e| e.to_string())?,
+            PathBuf::from(path),
+          ));
+        }
+        "--autosave" => {
+          config.autosave = Some(PathBuf::from(value(args.next(), "--autosave", "<file>")?));
+        }
+        "--check-invariants" => config.check_invariants = true,
+        _ => return Err(Msg::UnknownOption(&arg).to_string()),
+      }
+    }
+
+    Ok(config)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(args: &[&str]) -> Result<Config, String> {
+    Config::parse(args.iter().map(|a| a.to_string()))
+  }
+
+  #[test]
+  fn map_size_and_seed_come_from_the_command_line() {
+    let config = parse(&["--width", "80", "--height", "30", "--seed", "7"]).unwrap();
+    assert_eq!((config.width, config.height, config.seed), (80, 30, 7));
+    assert!(parse(&["--width", "large"]).is_err());
+    assert!(parse(&["--seed"]).is_err());
+  }
+}
@@ -0,0 +1,122 @@
+use crate::game::outposts::{OUTPOST_ENERGY_COST, OUTPOST_MINERAL_COST};
+use crate::utils::locale::{Arg, Msg};
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+// Erreurs qui interrompent le jeu ou l'un de ses outils, affichées dans la langue du joueur
+#[derive(Debug, Error)]
+pub enum GameError {
+  // Option de la ligne de commande, message déjà traduit
+  #[error("{0}")]
+  Config(String),
+  #[error("{}", Msg::MapTooSmall { width: *.width, height: *.height, min: *.min })]
+  MapTooSmall {
+    width: usize,
+    height: usize,
+    min: (usize, usize),
+  },
+  // Script Rhai introuvable ou qui ne compile pas
+  #[error("{0}")]
+  Script(String),
+  #[error("{}", Msg::SaveFailed(&format!("{}: {}", .path.display(), .source)))]
+  Save { path: PathBuf, source: io::Error },
+  #[error("{}", Msg::TerminalFailed(&.0.to_string()))]
+  Terminal(#[from] io::Error),
+  #[error("{}", Msg::TerminalFailed(&.0.to_string()))]
+  Screen(#[from] clearscreen::Error),
+  #[error("{}", Msg::TerminalFailed(&.0.to_string()))]
+  Signal(#[from] ctrlc::Error),
+}
+
+// Raison pour laquelle un robot ne peut pas faire le pas demandé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MoveError {
+  #[error("{}", Msg::MoveOutOfMap)]
+  OutOfMap,
+  #[error("{}", Msg::CellOccupied)]
+  Occupied,
+  #[error("{}", Msg::HiddenResource)]
+  Hidden,
+  #[error("{}", Msg::MoveBlocked)]
+  Blocked,
+}
+
+// Raison pour laquelle un robot laisse la ressource sur laquelle il se trouve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum CollectError {
+  #[error("{}", Msg::NothingToCollect)]
+  Nothing,
+  #[error("{}", Msg::ResourceLocked)]
+  Locked,
+  #[error("{}", Msg::CargoRefused)]
+  NotAccepted,
+  #[error("{}", Msg::CargoFull)]
+  Full,
+}
+
+// Raison pour laquelle un avant-poste ne peut pas être construit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum OutpostError {
+  #[error("{}", Msg::OutpostNotBuildable(*.x, *.y))]
+  NotBuildable { x: usize, y: usize },
+  #[error(
+    "{}",
+    Msg::OutpostTooExpensive(OUTPOST_MINERAL_COST, OUTPOST_ENERGY_COST)
+  )]
+  TooExpensive,
+}
+
+// Raison pour laquelle un stock ne peut pas passer d'une station à une autre
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum TransferError {
+  #[error("{}", Msg::NoSuchStation(*.0))]
+  NoSuchStation(usize),
+  #[error("{}", Msg::SameStation)]
+  SameStation,
+  #[error("{}", Msg::NotEnoughStock(*.station, *.available))]
+  NotEnough { station: usize, available: u32 },
+}
+
+// Commande de la console refusée, à la lecture ou à l'exécution
+#[derive(Debug, Error)]
+pub enum CommandError {
+  #[error("{}", Msg::EmptyCommand)]
+  Empty,
+  #[error("{}", Msg::UnknownCommand(.0))]
+  UnknownCommand(String),
+  #[error("{}", Msg::MissingArg(*.0))]
+  MissingArg(Arg),
+  #[error("{}", Msg::InvalidArg(*.0, .1))]
+  InvalidArg(Arg, String),
+  #[error("{}", Msg::ExtraArgument(.0))]
+  ExtraArgument(String),
+  #[error("{}", Msg::RobotsNumberedFromOne)]
+  RobotsNumberedFromOne,
+  #[error("{}", Msg::UnknownRobotType(.0))]
+  UnknownRobotType(String),
+  #[error("{}", Msg::UnknownResource(.0))]
+  UnknownResource(String),
+  #[error("{}", Msg::OutOfMap(*.x, *.y))]
+  OutOfMap { x: usize, y: usize },
+  #[error("{}", Msg::NoSuchRobot(*.0))]
+  NoSuchRobot(usize),
+  #[error("{}", Msg::UnknownZone(.0))]
+  UnknownZone(String),
+  #[error("{}", Msg::NotPassable(*.x, *.y))]
+  NotPassable { x: usize, y: usize },
+  #[error("{}", Msg::NotBuildable(*.x, *.y))]
+  NotBuildable { x: usize, y: usize },
+  #[error("{}", Msg::AlreadyQueued(*.x, *.y))]
+  AlreadyQueued { x: usize, y: usize },
+  #[error("{}", Msg::NotQueued(*.x, *.y))]
+  NotQueued { x: usize, y: usize },
+  #[error(transparent)]
+  Move(#[from] MoveError),
+  #[error(transparent)]
+  Transfer(#[from] TransferError),
+  #[error(transparent)]
+  Save(#[from] GameError),
+  #[error("{}", Msg::ExportFailed(&.0.to_string()))]
+  Export(io::Error),
+}
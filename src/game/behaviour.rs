@@ -0,0 +1,275 @@
+use crate::game::construction::{approach_cell, within_reach};
+use crate::game::game_automation::next_step_towards;
+use crate::map::flow::{FlowTarget, Hop};
+use crate::robot::robot::{ResourceType, Robot, RobotTask, RobotType};
+use crate::{Map, Station};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+
+// Ce qu'une stratégie sait du robot qu'elle pilote
+pub struct RobotView<'a> {
+  pub index: usize,
+  pub x: usize,
+  pub y: usize,
+  pub robot_type: RobotType,
+  pub inventory: &'a HashMap<ResourceType, u32>,
+  // Charge pondérée de la soute et sa capacité
+  pub load: u32,
+  pub capacity: u32,
+  pub full: bool,
+}
+
+impl<'a> RobotView<'a> {
+  pub fn new(index: usize, robot: &'a Robot) -> Self {
+    Self {
+      index,
+      x: robot.x,
+      y: robot.y,
+      robot_type: robot.robot_type,
+      inventory: &robot.inventory,
+      load: robot.load(),
+      capacity: robot.hold.capacity,
+      full: robot.is_full(),
+    }
+  }
+
+  pub fn inventory_count(&self) -> usize {
+    self.inventory.values().map(|qty| *qty as usize).sum()
+  }
+}
+
+// Ce qu'une stratégie sait du monde
+pub struct WorldView<'a> {
+  pub map: &'a Map,
+  pub stations: &'a [Station],
+  pub resources_revealed: bool,
+  pub other_robots: &'a [(usize, usize)],
+  pub construction_queue: &'a VecDeque<(usize, usize)>,
+}
+
+// Décision d'une stratégie pour un pas de mouvement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+  Idle,
+  // Garde sa tâche sans bouger (arrivé, ou aucun chemin pour l'instant)
+  Hold(RobotTask),
+  Step(RobotTask, isize, isize),
+}
+
+pub trait RobotBehaviour: Send + Sync {
+  fn name(&self) -> &str;
+  fn decide(&self, robot: &RobotView, world: &WorldView) -> Action;
+
+  // Appelé une fois par tour, juste avant les décisions, sur la carte qu'elles verront
+  fn begin_tick(&self, _map: &Map, _resources_revealed: bool) {}
+
+  // Erreurs survenues depuis le dernier appel, remontées dans le journal
+  fn take_errors(&self) -> Vec<String> {
+    Vec::new()
+  }
+}
+
+// Se dirige vers la cible, ou attend s'il n'y a pas de chemin
+fn head_to(
+  task: RobotTask,
+  robot: &RobotView,
+  world: &WorldView,
+  target: (usize, usize),
+) -> Action {
+  match next_step_towards(
+    robot.x,
+    robot.y,
+    target.0,
+    target.1,
+    world.map,
+    world.resources_revealed,
+  ) {
+    Some((dx, dy)) => Action::Step(task, dx, dy),
+    None => Action::Hold(task),
+  }
+}
+
+// Suit le champ de distances de la carte vers la destination qu'il désigne
+fn follow(task: impl FnOnce((usize, usize)) -> RobotTask, hop: Hop) -> Action {
+  let task = task(hop.target);
+  match hop.step {
+    Some((dx, dy)) => Action::Step(task, dx, dy),
+    None => Action::Hold(task),
+  }
+}
+
+// Destination la plus proche du type demandé
+fn nearest(robot: &RobotView, world: &WorldView, target: FlowTarget) -> Option<Hop> {
+  world.map.flow_hop(target, robot.x, robot.y)
+}
+
+// Les robots déchargent à la station atteignable la plus proche
+pub fn return_to_nearest_station(robot: &RobotView, world: &WorldView) -> Action {
+  let Some(hop) = nearest(robot, world, FlowTarget::Station) else {
+    return Action::Idle;
+  };
+  let Some(i) = world.stations.iter().position(|s| (s.x, s.y) == hop.target) else {
+    return Action::Idle;
+  };
+  follow(|_| RobotTask::Returning(i), hop)
+}
+
+pub struct ExploratorBehaviour;
+
+impl RobotBehaviour for ExploratorBehaviour {
+  fn name(&self) -> &str {
+    "explorator"
+  }
+
+  fn decide(&self, robot: &RobotView, world: &WorldView) -> Action {
+    // Si le robot a déjà de la science, ou plus de place pour en ramasser, il retourne à la station
+    if robot.inventory.contains_key(&ResourceType::Science) || robot.full {
+      return return_to_nearest_station(robot, world);
+    }
+    // Sinon, cherche la science la plus proche
+    match nearest(robot, world, FlowTarget::Science) {
+      Some(hop) => follow(|(tx, ty)| RobotTask::Exploring(tx, ty), hop),
+      None => Action::Idle,
+    }
+  }
+}
+
+pub struct CollectorBehaviour;
+
+impl RobotBehaviour for CollectorBehaviour {
+  fn name(&self) -> &str {
+    "collector"
+  }
+
+  fn decide(&self, robot: &RobotView, world: &WorldView) -> Action {
+    // Si l'inventaire est plein, retourne à la station
+    if robot.full {
+      return return_to_nearest_station(robot, world);
+    }
+    // Sinon, cherche le minerai ou l'énergie la plus proche
+    match nearest(robot, world, FlowTarget::Mineral)
+      .or_else(|| nearest(robot, world, FlowTarget::Energy))
+    {
+      Some(hop) => follow(|(tx, ty)| RobotTask::Collecting(tx, ty), hop),
+      None => Action::Idle,
+    }
+  }
+}
+
+// Variante : le gisement le plus proche, quel qu'il soit, plutôt que le minerai d'abord
+pub struct NearestCollectorBehaviour;
+
+impl RobotBehaviour for NearestCollectorBehaviour {
+  fn name(&self) -> &str {
+    "collector-nearest"
+  }
+
+  fn decide(&self, robot: &RobotView, world: &WorldView) -> Action {
+    if robot.full {
+      return return_to_nearest_station(robot, world);
+    }
+    let closest = [FlowTarget::Mineral, FlowTarget::Energy]
+      .into_iter()
+      .filter_map(|target| nearest(robot, world, target))
+      .min_by_key(|hop| hop.distance);
+    match closest {
+      Some(hop) => follow(|(tx, ty)| RobotTask::Collecting(tx, ty), hop),
+      None => Action::Idle,
+    }
+  }
+}
+
+pub struct BuilderBehaviour;
+
+impl RobotBehaviour for BuilderBehaviour {
+  fn name(&self) -> &str {
+    "builder"
+  }
+
+  fn decide(&self, robot: &RobotView, world: &WorldView) -> Action {
+    // Se rend à portée du prochain chantier de la file
+    let Some(&target) = world.construction_queue.front() else {
+      return Action::Idle;
+    };
+    let task = RobotTask::Building(target.0, target.1);
+    if within_reach(robot.x, robot.y, target) {
+      return Action::Hold(task);
+    }
+    match approach_cell(
+      robot.x,
+      robot.y,
+      target,
+      world.map,
+      world.resources_revealed,
+    ) {
+      Some(cell) => head_to(task, robot, world, cell),
+      None => Action::Hold(task),
+    }
+  }
+}
+
+// Flottes prédéfinies, comparées entre elles par le tournoi
+pub const STRATEGIES: [&str; 2] = ["default", "nearest"];
+
+// Stratégie de chaque robot : celle qui lui est propre, sinon celle de son type
+// Tables ordonnées : `all` parcourt les stratégies dans le même ordre à chaque partie
+pub struct BehaviourRegistry {
+  by_type: BTreeMap<RobotType, Arc<dyn RobotBehaviour>>,
+  by_robot: BTreeMap<usize, Arc<dyn RobotBehaviour>>,
+}
+
+impl Default for BehaviourRegistry {
+  fn default() -> Self {
+    let mut by_type: BTreeMap<RobotType, Arc<dyn RobotBehaviour>> = BTreeMap::new();
+    by_type.insert(RobotType::Explorator, Arc::new(ExploratorBehaviour));
+    by_type.insert(RobotType::Collector, Arc::new(CollectorBehaviour));
+    by_type.insert(RobotType::Builder, Arc::new(BuilderBehaviour));
+    Self {
+      by_type,
+      by_robot: BTreeMap::new(),
+    }
+  }
+}
+
+impl BehaviourRegistry {
+  pub fn named(name: &str) -> Option<Self> {
+    let mut registry = Self::default();
+    match name {
+      "default" => {}
+      "nearest" => registry.set_for_type(RobotType::Collector, Arc::new(NearestCollectorBehaviour)),
+      _ => return None,
+    }
+    Some(registry)
+  }
+
+  pub fn set_for_type(&mut self, robot_type: RobotType, behaviour: Arc<dyn RobotBehaviour>) {
+    self.by_type.insert(robot_type, behaviour);
+  }
+
+  pub fn set_for_robot(&mut self, index: usize, behaviour: Arc<dyn RobotBehaviour>) {
+    self.by_robot.insert(index, behaviour);
+  }
+
+  pub fn clear_robot(&mut self, index: usize) {
+    self.by_robot.remove(&index);
+  }
+
+  // Chaque stratégie enregistrée, une seule fois même si elle sert à plusieurs robots
+  pub fn all(&self) -> Vec<&dyn RobotBehaviour> {
+    let mut all: Vec<&Arc<dyn RobotBehaviour>> = Vec::new();
+    for behaviour in self.by_type.values().chain(self.by_robot.values()) {
+      if !all.iter().any(|b| Arc::ptr_eq(b, behaviour)) {
+        all.push(behaviour);
+      }
+    }
+    all.into_iter().map(|b| b.as_ref()).collect()
+  }
+
+  pub fn get(&self, index: usize, robot_type: RobotType) -> Option<&dyn RobotBehaviour> {
+    self
+      .by_robot
+      .get(&index)
+      .or_else(|| self.by_type.get(&robot_type))
+      .map(|b| b.as_ref())
+  }
+}
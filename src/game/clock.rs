@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+// Nombre maximal de tours simulés par image en vitesse maximale
+pub const MAX_TICKS_PER_FRAME: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimSpeed {
+  X1,
+  X2,
+  X4,
+  Max,
+}
+
+impl SimSpeed {
+  pub fn label(&self) -> &'static str {
+    match self {
+      SimSpeed::X1 => "x1",
+      SimSpeed::X2 => "x2",
+      SimSpeed::X4 => "x4",
+      SimSpeed::Max => "max",
+    }
+  }
+}
+
+// Horloge de la simulation, indépendante de la fréquence d'affichage
+#[derive(Debug)]
+pub struct SimClock {
+  pub base_tick_ms: u64,
+  pub speed: SimSpeed,
+  pub paused: bool,
+  accumulator: Duration,
+  pending_steps: u32,
+}
+
+impl SimClock {
+  pub fn new(base_tick_ms: u64) -> Self {
+    SimClock {
+      base_tick_ms,
+      speed: SimSpeed::X1,
+      paused: false,
+      accumulator: Duration::ZERO,
+      pending_steps: 0,
+    }
+  }
+
+  // Durée réelle d'un tour à la vitesse courante (nulle en vitesse maximale)
+  pub fn tick_duration(&self) -> Duration {
+    let base = Duration::from_millis(self.base_tick_ms.max(1));
+    match self.speed {
+      SimSpeed::X1 => base,
+      SimSpeed::X2 => base / 2,
+      SimSpeed::X4 => base / 4,
+      SimSpeed::Max => Duration::ZERO,
+    }
+  }
+
+  pub fn set_speed(&mut self, speed: SimSpeed) {
+    self.speed = speed;
+    self.paused = false;
+    self.accumulator = Duration::ZERO;
+  }
+
+  pub fn toggle_pause(&mut self) {
+    self.paused = !self.paused;
+    self.accumulator = Duration::ZERO;
+  }
+
+  // Avance d'un seul tour, à utiliser en pause
+  pub fn request_step(&mut self) {
+    self.pending_steps += 1;
+  }
+
+  // Nombre de tours à simuler pour le temps réel écoulé depuis le dernier appel
+  pub fn ticks_due(&mut self, elapsed: Duration) -> u32 {
+    let steps = std::mem::take(&mut self.pending_steps);
+    if self.paused {
+      return steps;
+    }
+
+    let tick = self.tick_duration();
+    if tick.is_zero() {
+      return MAX_TICKS_PER_FRAME;
+    }
+
+    self.accumulator += elapsed;
+    let due = (self.accumulator.as_nanos() / tick.as_nanos()) as u32;
+    self.accumulator -= tick * due;
+    // Évite la spirale de rattrapage après une longue pause de l'affichage
+    steps + due.min(MAX_TICKS_PER_FRAME)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+  }
+
+  #[test]
+  fn ticks_follow_real_time_and_speed() {
+    let mut clock = SimClock::new(100);
+    assert_eq!(clock.ticks_due(ms(250)), 2);
+    // Les 50 ms restantes comptent pour le tour suivant
+    assert_eq!(clock.ticks_due(ms(50)), 1);
+
+    clock.set_speed(SimSpeed::X4);
+    assert_eq!(clock.ticks_due(ms(100)), 4);
+
+    clock.set_speed(SimSpeed::Max);
+    assert_eq!(clock.ticks_due(ms(1)), MAX_TICKS_PER_FRAME);
+
+    clock.set_speed(SimSpeed::X1);
+    assert_eq!(clock.ticks_due(ms(1_000_000)), MAX_TICKS_PER_FRAME);
+  }
+
+  #[test]
+  fn a_paused_clock_only_plays_requested_steps() {
+    let mut clock = SimClock::new(100);
+    clock.toggle_pause();
+    assert_eq!(clock.ticks_due(ms(1000)), 0);
+    clock.request_step();
+    clock.request_step();
+    assert_eq!(clock.ticks_due(ms(1000)), 2);
+    assert_eq!(clock.ticks_due(ms(1000)), 0);
+
+    clock.toggle_pause();
+    clock.request_step();
+    assert_eq!(clock.ticks_due(ms(100)), 2);
+  }
+}
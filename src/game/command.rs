@@ -0,0 +1,479 @@
+use crate::GameState;
+use crate::error::{CommandError, MoveError};
+use crate::game::construction::build_cost;
+use crate::game::outposts::transfer;
+use crate::game::save::save_game;
+use crate::map::cell::Cell;
+use crate::robot::robot::{ResourceType, Robot, RobotType};
+use crate::utils::display::describe_cell;
+use crate::utils::locale::{Arg, Msg};
+use crate::utils::theme::Theme;
+use std::path::Path;
+
+// Commandes de la console, ouverte avec `:`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+  Goto {
+    robot: usize,
+    x: usize,
+    y: usize,
+  },
+  Reveal,
+  Unlock(String),
+  Spawn {
+    robot_type: RobotType,
+    x: usize,
+    y: usize,
+  },
+  Give {
+    resource: ResourceType,
+    qty: u32,
+  },
+  // Stations numérotées comme dans le panneau : 0 pour la principale
+  Transfer {
+    from: usize,
+    to: usize,
+    resource: ResourceType,
+    qty: u32,
+  },
+  Speed(u64),
+  Seed,
+  Save(String),
+  Export(String),
+  Inspect {
+    x: usize,
+    y: usize,
+  },
+  // File de construction : ajout, retrait et passage en tête d'un chantier
+  Build {
+    x: usize,
+    y: usize,
+  },
+  Cancel {
+    x: usize,
+    y: usize,
+  },
+  First {
+    x: usize,
+    y: usize,
+  },
+  Help,
+}
+
+pub const COMMAND_NAMES: [&str; 15] = [
+  "goto", "reveal", "unlock", "spawn", "give", "transfer", "speed", "seed", "save", "export",
+  "inspect", "build", "cancel", "first", "help",
+];
+const ROBOT_TYPES: [&str; 3] = ["collector", "explorator", "builder"];
+const RESOURCES: [&str; 3] = ["mineral", "energy", "science"];
+
+fn parse_number<T: std::str::FromStr>(word: Option<&str>, what: Arg) -> Result<T, CommandError> {
+  let word = missing(word, what)?;
+  word
+    .parse()
+    .map_err(|_| CommandError::InvalidArg(what, word.to_string()))
+}
+
+fn missing(word: Option<&str>, what: Arg) -> Result<&str, CommandError> {
+  word.ok_or(CommandError::MissingArg(what))
+}
+
+pub fn parse_robot_type(word: &str) -> Result<RobotType, CommandError> {
+  match word {
+    "collector" => Ok(RobotType::Collector),
+    "explorator" | "explorer" => Ok(RobotType::Explorator),
+    "builder" => Ok(RobotType::Builder),
+    _ => Err(CommandError::UnknownRobotType(word.to_string())),
+  }
+}
+
+fn parse_resource(word: &str) -> Result<ResourceType, CommandError> {
+  match word {
+    "mineral" => Ok(ResourceType::Mineral),
+    "energy" => Ok(ResourceType::Energy),
+    "science" => Ok(ResourceType::Science),
+    _ => Err(CommandError::UnknownResource(word.to_string())),
+  }
+}
+
+pub fn parse_command(input: &str) -> Result<Command, CommandError> {
+  let mut words = input.split_whitespace();
+  let Some(name) = words.next() else {
+    return Err(CommandError::Empty);
+  };
+
+  let command = match name {
+    "goto" => {
+      let robot: usize = parse_number(words.next(), Arg::RobotNumber)?;
+      Command::Goto {
+        robot: robot
+          .checked_sub(1)
+          .ok_or(CommandError::RobotsNumberedFromOne)?,
+        x: parse_number(words.next(), Arg::X)?,
+        y: parse_number(words.next(), Arg::Y)?,
+      }
+    }
+    "reveal" => Command::Reveal,
+    "unlock" => {
+      let zone: Vec<&str> = words.by_ref().collect();
+      if zone.is_empty() {
+        return Err(CommandError::MissingArg(Arg::Zone));
+      }
+      Command::Unlock(zone.join(" "))
+    }
+    "spawn" => Command::Spawn {
+      robot_type: parse_robot_type(missing(words.next(), Arg::RobotType)?)?,
+      x: parse_number(words.next(), Arg::X)?,
+      y: parse_number(words.next(), Arg::Y)?,
+    },
+    "give" => Command::Give {
+      resource: parse_resource(missing(words.next(), Arg::Resource)?)?,
+      qty: parse_number(words.next(), Arg::Quantity)?,
+    },
+    "transfer" => Command::Transfer {
+      from: parse_number(words.next(), Arg::Station)?,
+      to: parse_number(words.next(), Arg::Station)?,
+      resource: parse_resource(missing(words.next(), Arg::Resource)?)?,
+      qty: parse_number(words.next(), Arg::Quantity)?,
+    },
+    "speed" => Command::Speed(parse_number(words.next(), Arg::Speed)?),
+    "seed" => Command::Seed,
+    "save" => Command::Save(missing(words.next(), Arg::File)?.to_string()),
+    "export" => Command::Export(missing(words.next(), Arg::File)?.to_string()),
+    "inspect" => Command::Inspect {
+      x: parse_number(words.next(), Arg::X)?,
+      y: parse_number(words.next(), Arg::Y)?,
+    },
+    "build" => Command::Build {
+      x: parse_number(words.next(), Arg::X)?,
+      y: parse_number(words.next(), Arg::Y)?,
+    },
+    "cancel" => Command::Cancel {
+      x: parse_number(words.next(), Arg::X)?,
+      y: parse_number(words.next(), Arg::Y)?,
+    },
+    "first" => Command::First {
+      x: parse_number(words.next(), Arg::X)?,
+      y: parse_number(words.next(), Arg::Y)?,
+    },
+    "help" => Command::Help,
+    _ => return Err(CommandError::UnknownCommand(name.to_string())),
+  };
+
+  match words.next() {
+    Some(extra) => Err(CommandError::ExtraArgument(extra.to_string())),
+    None => Ok(command),
+  }
+}
+
+fn check_in_bounds(state: &GameState, x: usize, y: usize) -> Result<(), CommandError> {
+  if x < state.map.width && y < state.map.height {
+    Ok(())
+  } else {
+    Err(CommandError::OutOfMap { x, y })
+  }
+}
+
+fn queue_position(state: &GameState, x: usize, y: usize) -> Result<usize, CommandError> {
+  state
+    .construction_queue
+    .iter()
+    .position(|&site| site == (x, y))
+    .ok_or(CommandError::NotQueued { x, y })
+}
+
+pub fn apply_command(
+  state: &mut GameState,
+  command: Command,
+  theme: &Theme,
+) -> Result<String, CommandError> {
+  match command {
+    Command::Goto { robot, x, y } => {
+      check_in_bounds(state, x, y)?;
+      let target = state
+        .robots
+        .get_mut(robot)
+        .ok_or(CommandError::NoSuchRobot(robot + 1))?;
+      target.order = Some((x, y));
+      Ok(Msg::RobotSentTo(robot + 1, x, y).to_string())
+    }
+    Command::Reveal => {
+      state.resources_revealed = !state.resources_revealed;
+      Ok(Msg::ResourcesRevealed(state.resources_revealed).to_string())
+    }
+    Command::Unlock(name) => {
+      let zone = state
+        .map
+        .zones
+        .iter()
+        .position(|z| z.name.eq_ignore_ascii_case(&name) || z.id.to_string() == name)
+        .ok_or_else(|| CommandError::UnknownZone(name.clone()))?;
+      state.map.unlock_zone(zone);
+      Ok(Msg::ZoneUnlocked(&state.map.zones[zone].name).to_string())
+    }
+    Command::Spawn { robot_type, x, y } => {
+      check_in_bounds(state, x, y)?;
+      if matches!(state.map.grid()[(x, y)], Cell::Wall | Cell::Obstacle) {
+        return Err(CommandError::NotPassable { x, y });
+      }
+      let on_station = state.stations.iter().any(|s| (s.x, s.y) == (x, y));
+      if !on_station && state.robots.iter().any(|r| (r.x, r.y) == (x, y)) {
+        return Err(MoveError::Occupied.into());
+      }
+      state.robots.push(Robot::new(robot_type, x, y));
+      Ok(Msg::RobotSpawned(state.robots.len(), x, y).to_string())
+    }
+    Command::Give { resource, qty } => {
+      *state.stations[0]
+        .inventory
+        .entry(resource.clone())
+        .or_insert(0) += qty;
+      Ok(Msg::ResourcesGiven(qty, &resource).to_string())
+    }
+    Command::Transfer {
+      from,
+      to,
+      resource,
+      qty,
+    } => {
+      transfer(&mut state.stations, from, to, resource.clone(), qty)?;
+      Ok(Msg::StockTransferred(qty, &resource, from, to).to_string())
+    }
+    Command::Speed(ms) => {
+      state.clock.base_tick_ms = ms.max(1);
+      Ok(Msg::TickInterval(ms).to_string())
+    }
+    Command::Seed => Ok(Msg::MapSeed(state.seed).to_string()),
+    Command::Save(path) => {
+      save_game(state, Path::new(&path))?;
+      Ok(Msg::GameSaved(&path).to_string())
+    }
+    Command::Export(path) => {
+      state
+        .stats
+        .export_csv(&state.map, &path)
+        .map_err(CommandError::Export)?;
+      Ok(Msg::StatsExported(&path).to_string())
+    }
+    Command::Inspect { x, y } => {
+      check_in_bounds(state, x, y)?;
+      let mut message = describe_cell(state, x, y, theme);
+      for (i, robot) in state.robots.iter().enumerate() {
+        if robot.x == x && robot.y == y {
+          message.push_str(&Msg::InspectedRobot(i + 1, &robot.task.to_string()).to_string());
+        }
+      }
+      Ok(message)
+    }
+    Command::Build { x, y } => {
+      check_in_bounds(state, x, y)?;
+      // Un obstacle est démoli, une case vide devient une route
+      let cost = build_cost(&state.map, x, y).ok_or(CommandError::NotBuildable { x, y })?;
+      if state.construction_queue.contains(&(x, y)) {
+        return Err(CommandError::AlreadyQueued { x, y });
+      }
+      state.construction_queue.push_back((x, y));
+      Ok(Msg::BuildQueued(x, y, cost, state.construction_queue.len()).to_string())
+    }
+    Command::Cancel { x, y } => {
+      let index = queue_position(state, x, y)?;
+      state.construction_queue.remove(index);
+      Ok(Msg::BuildCancelled(x, y).to_string())
+    }
+    Command::First { x, y } => {
+      let index = queue_position(state, x, y)?;
+      if let Some(site) = state.construction_queue.remove(index) {
+        state.construction_queue.push_front(site);
+      }
+      Ok(Msg::BuildPrioritized(x, y).to_string())
+    }
+    Command::Help => Ok(Msg::CommandsHelp(&COMMAND_NAMES.join(", ")).to_string()),
+  }
+}
+
+fn common_prefix<'a>(candidates: &[&'a str]) -> Option<&'a str> {
+  let first = candidates.first()?;
+  let len = candidates.iter().fold(first.len(), |len, c| {
+    first
+      .chars()
+      .zip(c.chars())
+      .take(len)
+      .take_while(|(a, b)| a == b)
+      .count()
+  });
+  let end = first
+    .char_indices()
+    .nth(len)
+    .map_or(first.len(), |(i, _)| i);
+  Some(&first[..end])
+}
+
+// Complète le dernier mot de la saisie avec le plus long préfixe commun des candidats
+pub fn complete(input: &str, zone_names: &[String]) -> String {
+  let words: Vec<&str> = input.split_whitespace().collect();
+  let ends_with_space = input.ends_with(' ');
+  let (done, partial) = match (words.split_last(), ends_with_space) {
+    (Some((last, rest)), false) => (rest.to_vec(), *last),
+    _ => (words.clone(), ""),
+  };
+
+  let zones: Vec<&str> = zone_names.iter().map(|z| z.as_str()).collect();
+  let options: &[&str] = match done.as_slice() {
+    [] => &COMMAND_NAMES,
+    ["spawn"] => &ROBOT_TYPES,
+    ["give"] | ["transfer", _, _] => &RESOURCES,
+    ["unlock"] => &zones,
+    _ => &[],
+  };
+
+  let candidates: Vec<&str> = options
+    .iter()
+    .copied()
+    .filter(|o| o.to_lowercase().starts_with(&partial.to_lowercase()))
+    .collect();
+  let Some(prefix) = common_prefix(&candidates) else {
+    return input.to_string();
+  };
+
+  let mut completed: Vec<&str> = done;
+  completed.push(prefix);
+  let mut result = completed.join(" ");
+  if candidates.len() == 1 {
+    result.push(' ');
+  }
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Config;
+  use crate::error::TransferError;
+  use crate::station::station::Station;
+
+  fn state() -> GameState {
+    GameState::try_new(&Config::default()).unwrap()
+  }
+
+  fn run(state: &mut GameState, input: &str) -> Result<String, CommandError> {
+    parse_command(input).and_then(|command| apply_command(state, command, &Theme::default()))
+  }
+
+  #[test]
+  fn the_build_queue_can_be_edited_from_the_console() {
+    let mut state = state();
+    let mut empty = (1..state.map.height - 1)
+      .flat_map(|y| (1..state.map.width - 1).map(move |x| (x, y)))
+      .filter(|&(x, y)| state.map.grid()[(x, y)] == Cell::Empty);
+    let (a, b) = (empty.next().unwrap(), empty.next().unwrap());
+
+    run(&mut state, &format!("build {} {}", a.0, a.1)).unwrap();
+    run(&mut state, &format!("build {} {}", b.0, b.1)).unwrap();
+    assert!(run(&mut state, &format!("build {} {}", a.0, a.1)).is_err());
+    assert!(run(&mut state, "build 0 0").is_err());
+    assert_eq!(Vec::from(state.construction_queue.clone()), vec![a, b]);
+
+    run(&mut state, &format!("first {} {}", b.0, b.1)).unwrap();
+    assert_eq!(Vec::from(state.construction_queue.clone()), vec![b, a]);
+
+    run(&mut state, &format!("cancel {} {}", b.0, b.1)).unwrap();
+    assert!(run(&mut state, &format!("cancel {} {}", b.0, b.1)).is_err());
+    assert_eq!(Vec::from(state.construction_queue.clone()), vec![a]);
+  }
+
+  #[test]
+  fn commands_are_parsed_with_their_arguments() {
+    assert_eq!(
+      parse_command("goto 2 5 3").ok(),
+      Some(Command::Goto {
+        robot: 1,
+        x: 5,
+        y: 3
+      })
+    );
+    assert_eq!(
+      parse_command("  spawn explorer 4 2 ").ok(),
+      Some(Command::Spawn {
+        robot_type: RobotType::Explorator,
+        x: 4,
+        y: 2
+      })
+    );
+    assert_eq!(
+      parse_command("unlock Nord Ouest").ok(),
+      Some(Command::Unlock("Nord Ouest".to_string()))
+    );
+    assert_eq!(
+      parse_command("first 3 1").ok(),
+      Some(Command::First { x: 3, y: 1 })
+    );
+
+    assert!(matches!(parse_command(""), Err(CommandError::Empty)));
+    assert!(matches!(
+      parse_command("goto 0 1 1"),
+      Err(CommandError::RobotsNumberedFromOne)
+    ));
+    assert!(matches!(
+      parse_command("goto 1 1"),
+      Err(CommandError::MissingArg(Arg::Y))
+    ));
+    assert!(matches!(
+      parse_command("give gold 3"),
+      Err(CommandError::UnknownResource(_))
+    ));
+    assert!(matches!(
+      parse_command("speed fast"),
+      Err(CommandError::InvalidArg(Arg::Speed, _))
+    ));
+    assert!(matches!(
+      parse_command("seed 42"),
+      Err(CommandError::ExtraArgument(_))
+    ));
+    assert!(matches!(
+      parse_command("dance"),
+      Err(CommandError::UnknownCommand(_))
+    ));
+  }
+
+  #[test]
+  fn completion_extends_the_last_word() {
+    let zones = ["Nord-Ouest".to_string(), "Nord-Est".to_string()];
+    assert_eq!(complete("spa", &zones), "spawn ");
+    // « spawn » et « speed » : rien de plus à ajouter
+    assert_eq!(complete("sp", &zones), "sp");
+    assert_eq!(complete("ca", &zones), "cancel ");
+    assert_eq!(complete("spawn c", &zones), "spawn collector ");
+    assert_eq!(complete("give ", &zones), "give ");
+    assert_eq!(complete("give e", &zones), "give energy ");
+    assert_eq!(complete("unlock no", &zones), "unlock Nord-");
+    assert_eq!(complete("goto 1 ", &zones), "goto 1 ");
+    assert_eq!(complete("xyz", &zones), "xyz");
+  }
+
+  #[test]
+  fn stocks_can_be_moved_between_stations_from_the_console() {
+    let mut state = state();
+    state.stations.push(Station::new(2, 2));
+    run(&mut state, "give energy 5").unwrap();
+    let before = state.stations[0].amount(&ResourceType::Energy);
+    run(&mut state, "transfer 0 1 energy 3").unwrap();
+    assert_eq!(state.stations[0].amount(&ResourceType::Energy), before - 3);
+    assert_eq!(state.stations[1].amount(&ResourceType::Energy), 3);
+
+    assert!(matches!(
+      run(&mut state, "transfer 1 0 energy 4"),
+      Err(CommandError::Transfer(TransferError::NotEnough { .. }))
+    ));
+    assert!(matches!(
+      run(&mut state, "transfer 1 2 energy 1"),
+      Err(CommandError::Transfer(TransferError::NoSuchStation(2)))
+    ));
+    assert!(matches!(
+      run(&mut state, "transfer 1 1 energy 1"),
+      Err(CommandError::Transfer(TransferError::SameStation))
+    ));
+    assert!(run(&mut state, "transfer 1 0 gold 1").is_err());
+    assert_eq!(state.stations[1].amount(&ResourceType::Energy), 3);
+    assert_eq!(complete("transfer 1 0 en", &[]), "transfer 1 0 energy ");
+  }
+}
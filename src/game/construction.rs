@@ -0,0 +1,232 @@
+use crate::game::game_automation::{distance_field, neighbours};
+use crate::game::outposts::consume_nearest;
+use crate::map::cell::Cell;
+use crate::map::grid::Grid;
+use crate::map::map::Map;
+use crate::robot::robot::ResourceType;
+use crate::station::station::Station;
+
+// Coût en minerai (pris dans les stations les plus proches) pour démolir un obstacle ou paver une case vide
+pub const DEMOLISH_COST: u32 = 3;
+pub const ROAD_COST: u32 = 1;
+
+pub enum BuildResult {
+  Built(Cell),
+  MissingMineral,
+  NotBuildable,
+}
+
+pub fn build_cost(map: &Map, x: usize, y: usize) -> Option<u32> {
+  if map.is_border(x, y) {
+    return None;
+  }
+  match map.grid()[(x, y)] {
+    Cell::Obstacle => Some(DEMOLISH_COST),
+    Cell::Empty => Some(ROAD_COST),
+    _ => None,
+  }
+}
+
+// Le constructeur travaille depuis la case cible ou une case voisine
+pub fn within_reach(x: usize, y: usize, target: (usize, usize)) -> bool {
+  x.abs_diff(target.0) + y.abs_diff(target.1) <= 1
+}
+
+pub fn try_build(map: &mut Map, stations: &mut [Station], target: (usize, usize)) -> BuildResult {
+  let (x, y) = target;
+  let Some(cost) = build_cost(map, x, y) else {
+    return BuildResult::NotBuildable;
+  };
+
+  if !consume_nearest(stations, target, ResourceType::Mineral, cost) {
+    return BuildResult::MissingMineral;
+  }
+
+  BuildResult::Built(map.set_cell(x, y, Cell::Road))
+}
+
+// Case voisine de la cible la plus proche du robot, d'où il pourra construire
+pub fn approach_cell(
+  robot_x: usize,
+  robot_y: usize,
+  target: (usize, usize),
+  map: &Map,
+  resources_revealed: bool,
+) -> Option<(usize, usize)> {
+  let dist = distance_field(map, &[(robot_x, robot_y)], resources_revealed);
+  neighbours(map, target.0, target.1)
+    .filter_map(|(x, y)| dist[(x, y)].map(|d| ((x, y), d)))
+    .min_by_key(|(_, d)| *d)
+    .map(|(pos, _)| pos)
+}
+
+// Obstacle à démolir et nombre de pas que sa démolition ferait gagner
+pub type Shortcut = ((usize, usize), u32);
+
+// Ressources que vont chercher les collecteurs, avec leur distance `dist` à la station
+// la plus proche
+pub fn collector_targets(
+  map: &Map,
+  dist: &Grid<Option<u32>>,
+  resources_revealed: bool,
+) -> Vec<(usize, usize, u32)> {
+  map
+    .grid()
+    .iter()
+    .filter_map(|((x, y), cell)| {
+      let wanted = matches!(cell, Cell::Mineral | Cell::Energy)
+        && (resources_revealed || map.is_resource_accessible(x, y));
+      wanted.then_some((x, y, dist[(x, y)]?))
+    })
+    .collect()
+}
+
+// Pas qu'il a fallu parcourir pour récolter `cost` unités, d'après l'aller-retour moyen
+// vers les ressources connues : une construction n'est rentable qu'au-delà
+pub fn payback_steps(targets: &[(usize, usize, u32)], cost: u32) -> u32 {
+  if targets.is_empty() {
+    return u32::MAX;
+  }
+  let round_trip = 2 * targets.iter().map(|&(_, _, d)| d).sum::<u32>() / targets.len() as u32;
+  cost * round_trip
+}
+
+// Obstacles classés selon le trajet qu'ils feraient gagner aux collecteurs, et seuil de
+// rentabilité d'une démolition. Un obstacle entre deux cases à distances d1 < d2 de la
+// station ne raccourcit rien si d2 <= d1 + 2 ; sinon, chaque ressource dont le chemin
+// passerait par lui fait gagner la différence à chaque aller-retour.
+fn rank_shortcuts(
+  map: &Map,
+  stations: &[Station],
+  resources_revealed: bool,
+) -> (Vec<Shortcut>, u32) {
+  let sources: Vec<(usize, usize)> = stations.iter().map(|s| (s.x, s.y)).collect();
+  let dist = distance_field(map, &sources, resources_revealed);
+  let targets = collector_targets(map, &dist, resources_revealed);
+  let mut suggestions = Vec::new();
+
+  for y in 1..map.height - 1 {
+    for x in 1..map.width - 1 {
+      if map.grid()[(x, y)] != Cell::Obstacle {
+        continue;
+      }
+
+      let openings: Vec<(usize, usize)> = neighbours(map, x, y)
+        .filter(|&(nx, ny)| dist[(nx, ny)].is_some())
+        .collect();
+      let around = openings.iter().filter_map(|&(nx, ny)| dist[(nx, ny)]);
+      let (Some(min), Some(max)) = (around.clone().min(), around.max()) else {
+        continue;
+      };
+      if max <= min + 2 {
+        continue;
+      }
+
+      // Passer par l'obstacle : rejoindre la case la plus proche, traverser, continuer
+      let beyond = distance_field(map, &openings, resources_revealed);
+      let saved: u32 = targets
+        .iter()
+        .filter_map(|&(rx, ry, rd)| {
+          let via = min + 2 + beyond[(rx, ry)]?;
+          (via < rd).then(|| 2 * (rd - via))
+        })
+        .sum();
+      if saved > 0 {
+        suggestions.push(((x, y), saved));
+      }
+    }
+  }
+
+  suggestions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+  (suggestions, payback_steps(&targets, DEMOLISH_COST))
+}
+
+// Les `limit` meilleurs raccourcis, avec le nombre de pas qu'ils feraient gagner
+pub fn suggest_shortcuts(
+  map: &Map,
+  stations: &[Station],
+  resources_revealed: bool,
+  limit: usize,
+) -> Vec<Shortcut> {
+  let mut suggestions = rank_shortcuts(map, stations, resources_revealed).0;
+  suggestions.truncate(limit);
+  suggestions
+}
+
+// Meilleur raccourci, seulement s'il rapporte plus de trajet que le minerai qu'il coûte
+pub fn worthwhile_shortcut(
+  map: &Map,
+  stations: &[Station],
+  resources_revealed: bool,
+) -> Option<Shortcut> {
+  let (suggestions, payback) = rank_shortcuts(map, stations, resources_revealed);
+  suggestions
+    .into_iter()
+    .next()
+    .filter(|&(_, saved)| saved > payback)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Le même couloir en S que pour la recherche de chemin
+  fn maze_rows() -> Vec<&'static str> {
+    vec![
+      "OOOOOOO", //
+      "O.....O", //
+      "OOOOO.O", //
+      "O.....O", //
+      "O.OOOOO", //
+      "O.....O", //
+      "OOOOOOO",
+    ]
+  }
+
+  #[test]
+  fn approach_cell_picks_the_nearest_side_of_the_target() {
+    let map = Map::from_rows(&["OOOOOOO", "O.....O", "O..O..O", "O.....O", "OOOOOOO"]);
+    assert_eq!(approach_cell(1, 2, (3, 2), &map, false), Some((2, 2)));
+    assert_eq!(approach_cell(5, 2, (3, 2), &map, false), Some((4, 2)));
+
+    let walled = Map::from_rows(&["OOOOOOO", "O.OO..O", "OOOOOOO"]);
+    assert_eq!(approach_cell(1, 1, (3, 1), &walled, false), None);
+  }
+
+  #[test]
+  fn shortcuts_are_ranked_by_collector_travel_saved() {
+    let mut rows = maze_rows();
+    rows[5] = "O....MO";
+    let map = Map::from_rows(&rows);
+    let stations = [Station::new(1, 1)];
+    // Le minerai est à 16 pas ; par (1, 2) ou (5, 4), il n'est plus qu'à 8
+    assert_eq!(
+      suggest_shortcuts(&map, &stations, true, 3),
+      vec![((1, 2), 16), ((5, 4), 16), ((2, 2), 12)]
+    );
+    // Un seul aller-retour gagné ne rembourse pas 3 minerais
+    assert_eq!(worthwhile_shortcut(&map, &stations, true), None);
+
+    // Sans ressource au-delà des obstacles, aucune démolition ne sert
+    rows[5] = "O.....O";
+    rows[1] = "O.M...O";
+    let near = Map::from_rows(&rows);
+    assert_eq!(suggest_shortcuts(&near, &stations, true, 5), vec![]);
+  }
+
+  #[test]
+  fn a_shortcut_to_a_whole_deposit_pays_back() {
+    let map = Map::from_rows(&[
+      "OOOOOOOOOOOO", //
+      "O..........O", //
+      "OOOOOOOOOO.O", //
+      "OMMMMMMMMM.O", //
+      "OOOOOOOOOOOO",
+    ]);
+    let stations = [Station::new(1, 1)];
+    assert_eq!(
+      worthwhile_shortcut(&map, &stations, true),
+      Some(((1, 2), 180))
+    );
+  }
+}
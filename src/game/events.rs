@@ -0,0 +1,16 @@
+use crate::map::cell::Cell;
+
+// Faits marquants d'un tour, consommables par les outils qui pilotent la simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimEvent {
+  // Obstacle démoli ou route posée ; `previous` est la case remplacée
+  Built { x: usize, y: usize, previous: Cell },
+  OutpostBuilt { station: usize },
+  Delivered { robot: usize, amount: u32 },
+  ZoneUnlocked { zone: usize },
+  // Fin d'un ordre du joueur, destination atteinte ou abandonnée
+  OrderFinished { robot: usize, reached: bool },
+  RobotReplanned { robot: usize },
+  // Erreur d'une stratégie scriptée (compilation, exécution, budget dépassé)
+  BehaviourError { behaviour: String, message: String },
+}
@@ -1,213 +1,415 @@
+use crate::error::CollectError;
+use crate::game::behaviour::{Action, BehaviourRegistry, RobotView, WorldView};
+use crate::game::construction::{
+  BuildResult, DEMOLISH_COST, try_build, within_reach, worthwhile_shortcut,
+};
+use crate::game::events::SimEvent;
+use crate::game::outposts::{
+  OUTPOST_SEARCH_TICKS, build_outpost, can_afford_outpost, suggest_outpost_site, total_amount,
+};
+use crate::game::stats::classify_activity;
 use crate::map::cell::Cell;
-use crate::robot::robot::{ResourceType, Robot, RobotType};
+use crate::map::grid::{Grid, Visited};
+use crate::robot::robot::{MAX_SPEED, ResourceType, Robot, RobotTask, RobotType};
+use crate::utils::locale::Msg;
 use crate::{GameState, Map, Station};
-use std::collections::{HashMap, VecDeque};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
 
 pub fn automate_all_robots(state: &mut GameState, automation_enabled: bool) {
   let robot_positions: Vec<(usize, usize)> = state.robots.iter().map(|r| (r.x, r.y)).collect();
+  // Messages du tour, affichés ensemble pour qu'un robot n'efface pas celui d'un autre
+  let mut messages = Vec::new();
 
-  for (i, robot) in state.robots.iter_mut().enumerate() {
-    let other_robots: Vec<(usize, usize)> = robot_positions
-      .iter()
-      .enumerate()
-      .filter(|(j, _)| *j != i)
-      .map(|(_, pos)| *pos)
-      .collect();
+  // Sans chantier en cours, propose le raccourci qui fait gagner le plus de trajet aux
+  // collecteurs, s'il rembourse le minerai de la démolition
+  if automation_enabled
+    && state.construction_queue.is_empty()
+    && total_amount(&state.stations, &ResourceType::Mineral) >= DEMOLISH_COST
+    && let Some((pos, _)) =
+      worthwhile_shortcut(&state.map, &state.stations, state.resources_revealed)
+  {
+    state.construction_queue.push_back(pos);
+  }
+
+  // Un avant-poste n'est construit que s'il est rentable ; la recherche, coûteuse,
+  // n'a lieu que de temps en temps
+  if automation_enabled
+    && state.stats.tick.is_multiple_of(OUTPOST_SEARCH_TICKS)
+    && can_afford_outpost(&state.stations)
+    && let Some(((x, y), _)) =
+      suggest_outpost_site(&state.map, &state.stations, state.resources_revealed)
+    && let Ok(station) = build_outpost(&state.map, &mut state.stations, x, y)
+  {
+    messages.push(Msg::OutpostBuilt(station, x, y).to_string());
+    state.events.push(SimEvent::OutpostBuilt { station });
+  }
+
+  // Les champs de distances suivent les avant-postes et la révélation des ressources
+  let stations: Vec<(usize, usize)> = state.stations.iter().map(|s| (s.x, s.y)).collect();
+  state
+    .map
+    .sync_flow_fields(&stations, state.resources_revealed);
+
+  for behaviour in state.behaviours.all() {
+    behaviour.begin_tick(&state.map, state.resources_revealed);
+  }
+
+  // Première phase : chaque robot planifie son tour en parallèle, sur l'état du début du tour
+  let snapshot: &GameState = state;
+  let plans: Vec<Plan> = snapshot
+    .robots
+    .par_iter()
+    .enumerate()
+    .map(|(i, robot)| plan_robot(i, robot, snapshot, &robot_positions, automation_enabled))
+    .collect();
+
+  // Seconde phase, dans l'ordre des robots : chacun suit son plan tant que la case visée
+  // est libre ; en cas de conflit, le premier arrivé passe et l'autre s'arrête pour ce tour
+  let mut positions = robot_positions.clone();
+  let mut speed_bonus = 0;
+  let mut deliveries = 0;
+  let mut activities = Vec::with_capacity(state.robots.len());
+  for ((i, robot), plan) in state.robots.iter_mut().enumerate().zip(plans) {
+    if plan.unreachable
+      && let Some(target) = robot.order.take()
+    {
+      messages.push(Msg::OrderUnreachable(target.0, target.1).to_string());
+      state.events.push(SimEvent::OrderFinished {
+        robot: i,
+        reached: false,
+      });
+    }
+
+    robot.task = plan.task;
+    robot.move_points = plan.move_points;
+    // L'ordre est terminé dès que le robot passe sur sa destination
+    let mut reached = order_reached(robot);
+    for &(dx, dy) in &plan.steps {
+      // La position d'un robot n'est jamais sa propre destination : `positions` peut l'inclure
+      let refused = robot
+        .try_move(
+          dx,
+          dy,
+          &state.map,
+          state.resources_revealed,
+          &positions,
+          &state.stations,
+        )
+        .is_err();
+      if refused {
+        robot.move_points = 0;
+        break;
+      }
+      positions[i] = (robot.x, robot.y);
+      state.map.record_visit(robot.x, robot.y);
+      reached = reached.or_else(|| order_reached(robot));
+    }
+    if let Some(target) = reached {
+      messages.push(Msg::DestinationReached(target.0, target.1).to_string());
+      state.events.push(SimEvent::OrderFinished {
+        robot: i,
+        reached: true,
+      });
+    }
+
+    if robot.robot_type == RobotType::Builder
+      && let Some(&target) = state.construction_queue.front()
+      && within_reach(robot.x, robot.y, target)
+    {
+      match try_build(&mut state.map, &mut state.stations, target) {
+        BuildResult::Built(previous) => {
+          state.construction_queue.pop_front();
+          state.events.push(SimEvent::Built {
+            x: target.0,
+            y: target.1,
+            previous,
+          });
+          messages.push(match previous {
+            Cell::Obstacle => Msg::ObstacleDemolished(target.0, target.1).to_string(),
+            _ => Msg::RoadBuilt(target.0, target.1).to_string(),
+          });
+        }
+        BuildResult::NotBuildable => {
+          state.construction_queue.pop_front();
+        }
+        BuildResult::MissingMineral => {}
+      }
+    }
+
+    // Déchargement automatique à la station sur laquelle se trouve le robot
+    let carried = robot.inventory_count() as u32;
+    if let Some(station) = state
+      .stations
+      .iter_mut()
+      .find(|s| s.x == robot.x && s.y == robot.y)
+    {
+      let unloaded = robot.unload_resources(station, &mut state.map);
+      for zone in unloaded.zones {
+        messages.push(Msg::ZoneUnlocked(&state.map.zones[zone].name).to_string());
+        state.events.push(SimEvent::ZoneUnlocked { zone });
+      }
+      if unloaded.science {
+        speed_bonus += 1;
+      }
+    }
+
+    let delivered = carried - robot.inventory_count() as u32;
+    deliveries += delivered;
+    if delivered > 0 {
+      state.events.push(SimEvent::Delivered {
+        robot: i,
+        amount: delivered,
+      });
+    }
+    let activity = classify_activity(robot, robot_positions[i], delivered > 0, &state.stations);
+    robot.telemetry.record(activity, delivered);
+    activities.push(activity);
 
-    if automation_enabled {
-      automate_robot(
+    if robot.telemetry.needs_replan() {
+      state.events.push(SimEvent::RobotReplanned { robot: i });
+      messages.push(replan(
+        i,
         robot,
         &state.map,
-        &state.station,
+        &state.stations,
         state.resources_revealed,
-        &other_robots,
-      );
+        &positions,
+      ));
+      positions[i] = (robot.x, robot.y);
     }
 
-    // Déchargement automatique à la station
-    if robot.x == state.station.x && robot.y == state.station.y {
-      let science_deposited = robot.unload_resources(&mut state.station, &mut state.map);
-      if science_deposited {
-        if let Some(msg) = state.map.unlock_zone_with_science((robot.x, robot.y)) {
-          println!("{msg}");
-        }
-        if state.robot_speed_ms > 30 {
-          state.robot_speed_ms -= 50;
-        }
+    // Un robot piloté à la main apprend pourquoi il laisse la ressource en place
+    match robot.collect_resource(&mut state.map, state.resources_revealed) {
+      Ok(ResourceType::Science) => messages.push(Msg::ScienceCollected.to_string()),
+      Err(e @ (CollectError::Full | CollectError::NotAccepted)) if !robot.automated => {
+        messages.push(e.to_string())
       }
+      _ => {}
+    }
+  }
+
+  // Les erreurs des scripts vont au journal sans interrompre la partie
+  for behaviour in state.behaviours.all() {
+    for message in behaviour.take_errors() {
+      messages.push(Msg::ScriptError(behaviour.name(), &message).to_string());
+      state.events.push(SimEvent::BehaviourError {
+        behaviour: behaviour.name().to_string(),
+        message,
+      });
     }
+  }
 
-    state.last_collect_message = robot.collect_resource(&mut state.map, state.resources_revealed);
+  if !messages.is_empty() {
+    state.last_collect_message = Some(messages.join(" · "));
+  }
+
+  state
+    .stats
+    .record(&state.map, &state.stations, &activities, deliveries);
+
+  // Chaque livraison de science améliore la vitesse de toute la flotte
+  for robot in &mut state.robots {
+    robot.speed = (robot.speed + speed_bonus).min(MAX_SPEED);
   }
 }
 
+// La stratégie enregistrée pour le robot décide, puis le robot exécute
 pub fn automate_robot(
+  index: usize,
   robot: &mut Robot,
-  map: &Map,
-  station: &Station,
-  resources_revealed: bool,
-  other_robots: &[(usize, usize)],
+  behaviours: &BehaviourRegistry,
+  world: &WorldView,
 ) {
-  match robot.robot_type {
-    RobotType::Explorator => {
-      // Si le robot a déjà de la science, il retourne à la station
-      if robot.inventory.contains_key(&ResourceType::Science) {
-        if let Some((dx, dy)) = next_step_towards(
-          robot.x,
-          robot.y,
-          station.x,
-          station.y,
-          map,
-          resources_revealed,
-        ) {
-          robot.try_move(dx, dy, map, resources_revealed, other_robots, station);
-        }
-        return;
-      }
-      // Sinon, cherche la science la plus proche
-      if let Some((tx, ty)) = find_nearest(robot.x, robot.y, map, Cell::Science, resources_revealed)
-      {
-        if let Some((dx, dy)) = next_step_towards(robot.x, robot.y, tx, ty, map, resources_revealed)
-        {
-          robot.try_move(dx, dy, map, resources_revealed, other_robots, station);
-        }
-      }
-    }
-    RobotType::Collector => {
-      // Si l'inventaire est plein, retourne à la station
-      if robot.inventory_count() >= robot.inventory_capacity {
-        if let Some((dx, dy)) = next_step_towards(
-          robot.x,
-          robot.y,
-          station.x,
-          station.y,
-          map,
-          resources_revealed,
-        ) {
-          robot.try_move(dx, dy, map, resources_revealed, other_robots, station);
-        }
-        return;
-      }
-      // Sinon, cherche le minerai ou l'énergie la plus proche
-      if let Some((tx, ty)) =
-        find_nearest_with_access_check(robot.x, robot.y, map, Cell::Mineral, resources_revealed)
-          .or_else(|| {
-            find_nearest_with_access_check(robot.x, robot.y, map, Cell::Energy, resources_revealed)
-          })
-      {
-        if let Some((dx, dy)) = next_step_towards(robot.x, robot.y, tx, ty, map, resources_revealed)
-        {
-          robot.try_move(dx, dy, map, resources_revealed, other_robots, station);
-        }
-      }
+  let action = match behaviours.get(index, robot.robot_type) {
+    Some(behaviour) => behaviour.decide(&RobotView::new(index, robot), world),
+    None => Action::Idle,
+  };
+
+  match action {
+    Action::Idle => robot.task = RobotTask::Idle,
+    Action::Hold(task) => robot.task = task,
+    Action::Step(task, dx, dy) => {
+      robot.task = task;
+      robot
+        .try_move(
+          dx,
+          dy,
+          world.map,
+          world.resources_revealed,
+          world.other_robots,
+          world.stations,
+        )
+        .ok();
     }
   }
 }
 
-pub fn find_nearest_with_access_check(
-  start_x: usize,
-  start_y: usize,
-  map: &Map,
-  target: Cell,
-  resources_revealed: bool,
-) -> Option<(usize, usize)> {
-  let width = map.width;
-  let height = map.height;
-  let mut visited = vec![vec![false; width]; height];
-  let mut queue = VecDeque::new();
+// Ce qu'un robot prévoit pour ce tour
+pub struct Plan {
+  pub task: RobotTask,
+  pub steps: Vec<(isize, isize)>,
+  // Points de mouvement restants une fois tous les pas faits
+  pub move_points: i32,
+  // Ordre du joueur à abandonner faute de chemin
+  pub unreachable: bool,
+}
 
-  queue.push_back((start_x, start_y));
-  visited[start_y][start_x] = true;
+// Joue le tour du robot sur une copie, sans rien modifier : seuls les pas sont retenus
+pub fn plan_robot(
+  index: usize,
+  robot: &Robot,
+  state: &GameState,
+  robot_positions: &[(usize, usize)],
+  automation_enabled: bool,
+) -> Plan {
+  let other_robots: Vec<(usize, usize)> = robot_positions
+    .iter()
+    .enumerate()
+    .filter(|(j, _)| *j != index)
+    .map(|(_, pos)| *pos)
+    .collect();
+  let world = WorldView {
+    map: &state.map,
+    stations: &state.stations,
+    resources_revealed: state.resources_revealed,
+    other_robots: &other_robots,
+    construction_queue: &state.construction_queue,
+  };
 
-  while let Some((x, y)) = queue.pop_front() {
-    let cell = &map.grid[y][x];
+  let mut ghost = robot.clone();
+  let mut steps = Vec::new();
+  let mut unreachable = false;
 
-    if *cell == target {
-      let is_accessible = map.is_resource_accessible(x, y);
-      if resources_revealed || is_accessible {
-        return Some((x, y));
-      }
+  // Le robot avance tant qu'il lui reste des points de mouvement
+  ghost.move_points += ghost.speed as i32;
+  while ghost.move_points > 0 {
+    let before = (ghost.x, ghost.y);
+
+    if let Some(target) = ghost.order {
+      // Un ordre du joueur passe avant l'automatisation
+      follow_order(&mut ghost, target, &world);
+      unreachable |= ghost.order.is_none() && (ghost.x, ghost.y) != target;
+    } else if automation_enabled && ghost.automated {
+      automate_robot(index, &mut ghost, &state.behaviours, &world);
+    } else {
+      ghost.task = RobotTask::Manual;
     }
 
-    let directions = [(0isize, 1isize), (1, 0), (0, -1), (-1, 0)];
-    for (dx, dy) in directions.iter() {
-      let nx = (x as isize) + dx;
-      let ny = (y as isize) + dy;
-
-      if nx >= 0
-        && ny >= 0
-        && (nx as usize) < width
-        && (ny as usize) < height
-        && !visited[ny as usize][nx as usize]
-      {
-        let next_cell = &map.grid[ny as usize][nx as usize];
-        let is_accessible = map.is_resource_accessible(nx as usize, ny as usize);
-
-        let blocked = matches!(next_cell, Cell::Wall | Cell::Obstacle)
-          || (!resources_revealed
-            && !is_accessible
-            && matches!(next_cell, Cell::Mineral | Cell::Energy));
-
-        if !blocked {
-          visited[ny as usize][nx as usize] = true;
-          queue.push_back((nx as usize, ny as usize));
-        }
-      }
+    // Un robot immobile ne thésaurise pas ses points
+    if (ghost.x, ghost.y) == before {
+      ghost.move_points = 0;
+      break;
     }
+    steps.push((
+      ghost.x as isize - before.0 as isize,
+      ghost.y as isize - before.1 as isize,
+    ));
+    ghost.move_points -= state.map.grid()[(ghost.x, ghost.y)].move_cost();
+  }
+
+  Plan {
+    task: ghost.task,
+    steps,
+    move_points: ghost.move_points,
+    unreachable,
   }
-  None
 }
 
-pub fn find_nearest(
-  start_x: usize,
-  start_y: usize,
+// Avance d'un pas vers la cible de l'ordre, et l'efface une fois atteinte ou inaccessible
+fn follow_order(robot: &mut Robot, target: (usize, usize), world: &WorldView) {
+  if (robot.x, robot.y) != target {
+    let Some((dx, dy)) = next_step_towards(
+      robot.x,
+      robot.y,
+      target.0,
+      target.1,
+      world.map,
+      world.resources_revealed,
+    ) else {
+      robot.order = None;
+      robot.task = RobotTask::Idle;
+      return;
+    };
+    robot.task = RobotTask::GoingTo(target.0, target.1);
+    robot
+      .try_move(
+        dx,
+        dy,
+        world.map,
+        world.resources_revealed,
+        world.other_robots,
+        world.stations,
+      )
+      .ok();
+  }
+
+  if (robot.x, robot.y) == target {
+    robot.order = None;
+    robot.task = RobotTask::Idle;
+  }
+}
+
+// Efface l'ordre du robot arrivé à destination, et renvoie celle-ci
+fn order_reached(robot: &mut Robot) -> Option<(usize, usize)> {
+  let target = robot.order?;
+  if (robot.x, robot.y) != target {
+    return None;
+  }
+  robot.order = None;
+  Some(target)
+}
+
+// Le chien de garde : un robot bloqué trop longtemps abandonne son ordre
+// et tente un pas de côté pour sortir d'un embouteillage
+fn replan(
+  index: usize,
+  robot: &mut Robot,
   map: &Map,
-  target: Cell,
+  stations: &[Station],
   resources_revealed: bool,
-) -> Option<(usize, usize)> {
-  let width = map.width;
-  let height = map.height;
-  let mut visited = vec![vec![false; width]; height];
-  let mut queue = VecDeque::new();
+  other_robots: &[(usize, usize)],
+) -> String {
+  let dropped = robot.order.take().is_some();
 
-  queue.push_back((start_x, start_y));
-  visited[start_y][start_x] = true;
+  let sidestep = neighbours(map, robot.x, robot.y)
+    .find(|&(x, y)| is_passable(map, x, y, resources_revealed) && !other_robots.contains(&(x, y)));
+  if let Some((x, y)) = sidestep {
+    let (dx, dy) = (x as isize - robot.x as isize, y as isize - robot.y as isize);
+    robot
+      .try_move(dx, dy, map, resources_revealed, other_robots, stations)
+      .ok();
+  }
 
-  while let Some((x, y)) = queue.pop_front() {
-    let cell = &map.grid[y][x];
+  Msg::RobotBlocked {
+    index: index + 1,
+    ticks: robot.telemetry.blocked_streak,
+    dropped,
+  }
+  .to_string()
+}
 
-    if *cell == target {
-      if target == Cell::Mineral || target == Cell::Energy {
-        if resources_revealed {
-          return Some((x, y));
-        }
-      } else {
-        return Some((x, y));
-      }
-    }
+// Mémoire de travail des recherches, réutilisée d'une recherche à l'autre par chaque fil
+#[derive(Default)]
+struct SearchScratch {
+  visited: Visited,
+  parent: Vec<usize>,
+  queue: VecDeque<usize>,
+}
 
-    let directions = [(0isize, 1isize), (1, 0), (0, -1), (-1, 0)];
-    for (dx, dy) in directions.iter() {
-      let nx = (x as isize) + dx;
-      let ny = (y as isize) + dy;
-
-      if nx >= 0
-        && ny >= 0
-        && (nx as usize) < width
-        && (ny as usize) < height
-        && !visited[ny as usize][nx as usize]
-      {
-        let next_cell = &map.grid[ny as usize][nx as usize];
-        let blocked = matches!(next_cell, Cell::Wall | Cell::Obstacle)
-          || (!resources_revealed && matches!(next_cell, Cell::Mineral | Cell::Energy));
-        if !blocked {
-          visited[ny as usize][nx as usize] = true;
-          queue.push_back((nx as usize, ny as usize));
-        }
-      }
-    }
-  }
-  None
+thread_local! {
+  static SCRATCH: RefCell<SearchScratch> = RefCell::default();
+}
+
+fn with_scratch<R>(map: &Map, search: impl FnOnce(&mut SearchScratch) -> R) -> R {
+  SCRATCH.with_borrow_mut(|scratch| {
+    let len = map.grid().len();
+    scratch.visited.reset(len);
+    scratch.queue.clear();
+    scratch.parent.resize(len, 0);
+    search(scratch)
+  })
 }
 
 pub fn next_step_towards(
@@ -218,67 +420,167 @@ pub fn next_step_towards(
   map: &Map,
   resources_revealed: bool,
 ) -> Option<(isize, isize)> {
-  let width = map.width;
-  let height = map.height;
-  let mut visited = vec![vec![false; width]; height];
-  let mut queue = VecDeque::new();
-  let mut parent: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
-
-  queue.push_back((start_x, start_y));
-  visited[start_y][start_x] = true;
-
-  while let Some((x, y)) = queue.pop_front() {
-    if x == target_x && y == target_y {
-      let mut cur = (x, y);
-      let mut path = vec![cur];
-      while let Some(&p) = parent.get(&cur) {
-        path.push(p);
-        cur = p;
+  let path = find_path(
+    start_x,
+    start_y,
+    target_x,
+    target_y,
+    map,
+    resources_revealed,
+  )?;
+
+  let &(nx, ny) = path.get(1)?;
+  let dx = (nx as isize) - (start_x as isize);
+  let dy = (ny as isize) - (start_y as isize);
+  Some((dx, dy))
+}
+
+// Plus court chemin (BFS), départ et arrivée compris
+pub fn find_path(
+  start_x: usize,
+  start_y: usize,
+  target_x: usize,
+  target_y: usize,
+  map: &Map,
+  resources_revealed: bool,
+) -> Option<Vec<(usize, usize)>> {
+  let start = map.grid().index_of(start_x, start_y);
+  let target = map.grid().index_of(target_x, target_y);
+  // La cible elle-même reste atteignable si c'est une ressource accessible
+  let target_reachable = resources_revealed || map.is_resource_accessible(target_x, target_y);
+
+  with_scratch(map, |scratch| {
+    scratch.visited.insert(start);
+    scratch.queue.push_back(start);
+
+    while let Some(i) = scratch.queue.pop_front() {
+      if i == target {
+        let mut path = vec![map.grid().position(i)];
+        let mut cur = i;
+        while cur != start {
+          cur = scratch.parent[cur];
+          path.push(map.grid().position(cur));
+        }
+        path.reverse();
+        return Some(path);
       }
-      path.reverse();
-      if path.len() >= 2 {
-        let (nx, ny) = path[1];
-        let dx = (nx as isize) - (start_x as isize);
-        let dy = (ny as isize) - (start_y as isize);
-        return Some((dx, dy));
-      } else {
-        return None;
+
+      for next in map.grid().neighbour_indices(i) {
+        let passable =
+          !map.blocks(next, resources_revealed) || (next == target && target_reachable);
+        if !scratch.visited.contains(next) && passable {
+          scratch.visited.insert(next);
+          scratch.parent[next] = i;
+          scratch.queue.push_back(next);
+        }
       }
     }
+    None
+  })
+}
 
-    let directions = [(0isize, 1isize), (1, 0), (0, -1), (-1, 0)];
-    for (dx, dy) in directions.iter() {
-      let nx = (x as isize) + dx;
-      let ny = (y as isize) + dy;
-      if nx >= 0
-        && ny >= 0
-        && (nx as usize) < width
-        && (ny as usize) < height
-        && !visited[ny as usize][nx as usize]
-      {
-        let next_cell = &map.grid[ny as usize][nx as usize];
-
-        let is_accessible = map.is_resource_accessible(nx as usize, ny as usize);
-        let blocked = matches!(next_cell, Cell::Wall | Cell::Obstacle)
-          || (!resources_revealed
-            && !is_accessible
-            && matches!(next_cell, Cell::Mineral | Cell::Energy));
-
-        if !blocked {
-          visited[ny as usize][nx as usize] = true;
-          parent.insert((nx as usize, ny as usize), (x, y));
-          queue.push_back((nx as usize, ny as usize));
-        } else if (nx as usize) == target_x && (ny as usize) == target_y {
-          if is_accessible || resources_revealed {
-            visited[ny as usize][nx as usize] = true;
-            parent.insert((nx as usize, ny as usize), (x, y));
-            queue.push_back((nx as usize, ny as usize));
-          }
-        }
+pub fn is_passable(map: &Map, x: usize, y: usize, resources_revealed: bool) -> bool {
+  !map.blocks(map.grid().index_of(x, y), resources_revealed)
+}
+
+pub fn neighbours(map: &Map, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+  map.grid().neighbours(x, y)
+}
+
+// Distance BFS depuis la plus proche des cases de départ vers toutes les cases atteignables
+pub fn distance_field(
+  map: &Map,
+  sources: &[(usize, usize)],
+  resources_revealed: bool,
+) -> Grid<Option<u32>> {
+  let mut dist = Grid::new(map.width, map.height, None);
+  let mut queue = VecDeque::new();
+
+  for &(x, y) in sources {
+    let i = map.grid().index_of(x, y);
+    dist.cells_mut()[i] = Some(0);
+    queue.push_back(i);
+  }
+
+  while let Some(i) = queue.pop_front() {
+    let d = dist.cells()[i].unwrap_or(0);
+    for next in map.grid().neighbour_indices(i) {
+      if dist.cells()[next].is_none() && !map.blocks(next, resources_revealed) {
+        dist.cells_mut()[next] = Some(d + 1);
+        queue.push_back(next);
       }
     }
   }
 
-  println!("Aucun chemin trouvé vers ({}, {})", target_x, target_y);
-  None
+  dist
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Un couloir en S : le plus court chemin de (1, 1) à (5, 5) fait le tour des obstacles
+  fn maze() -> Map {
+    Map::from_rows(&[
+      "OOOOOOO", //
+      "O.....O", //
+      "OOOOO.O", //
+      "O.....O", //
+      "O.OOOOO", //
+      "O.....O", //
+      "OOOOOOO",
+    ])
+  }
+
+  #[test]
+  fn find_path_follows_the_corridor() {
+    let map = maze();
+    let path = find_path(1, 1, 5, 5, &map, false).unwrap();
+    assert_eq!(path.first(), Some(&(1, 1)));
+    assert_eq!(path.last(), Some(&(5, 5)));
+    assert_eq!(path.len(), 17);
+    for pair in path.windows(2) {
+      let ((ax, ay), (bx, by)) = (pair[0], pair[1]);
+      assert_eq!(ax.abs_diff(bx) + ay.abs_diff(by), 1);
+      assert!(is_passable(&map, bx, by, false));
+    }
+    assert_eq!(find_path(1, 1, 1, 1, &map, false), Some(vec![(1, 1)]));
+  }
+
+  #[test]
+  fn find_path_fails_when_walled_off() {
+    let map = Map::from_rows(&["OOOOO", "O.O.O", "OOOOO"]);
+    assert_eq!(find_path(1, 1, 3, 1, &map, true), None);
+    assert_eq!(next_step_towards(1, 1, 3, 1, &map, true), None);
+  }
+
+  #[test]
+  fn next_step_towards_takes_the_first_step() {
+    let map = maze();
+    assert_eq!(next_step_towards(1, 1, 5, 5, &map, false), Some((1, 0)));
+    assert_eq!(next_step_towards(5, 1, 5, 5, &map, false), Some((0, 1)));
+    assert_eq!(next_step_towards(5, 5, 5, 5, &map, false), None);
+  }
+
+  #[test]
+  fn hidden_resources_block_the_way_until_revealed() {
+    let map = Map::from_rows(&["OOOOOOO", "O..M..O", "OOOOOOO"]);
+    assert!(!is_passable(&map, 3, 1, false));
+    assert!(is_passable(&map, 3, 1, true));
+    assert_eq!(find_path(1, 1, 5, 1, &map, false), None);
+    // Cachée, elle n'est pas non plus une destination
+    assert_eq!(find_path(1, 1, 3, 1, &map, false), None);
+    assert_eq!(find_path(1, 1, 5, 1, &map, true).map(|p| p.len()), Some(5));
+  }
+
+  #[test]
+  fn distance_field_counts_steps_from_the_nearest_source() {
+    let map = maze();
+    let dist = distance_field(&map, &[(1, 1), (1, 5)], false);
+    assert_eq!(dist[(1, 1)], Some(0));
+    assert_eq!(dist[(5, 1)], Some(4));
+    assert_eq!(dist[(5, 5)], Some(4));
+    assert_eq!(dist[(3, 3)], Some(4));
+    assert_eq!(dist[(0, 0)], None);
+  }
 }
@@ -1,43 +1,188 @@
 use crate::GameState;
-use crate::game::game_automation::automate_all_robots;
-use crate::utils::display::{print_commands_and_indicators, print_map_stats};
+use crate::error::GameError;
+use crate::game::construction::suggest_shortcuts;
+use crate::game::save::save_game;
+use crate::game::simulation::Simulation;
+use crate::map::map::MapView;
+use crate::utils::display::{
+  print_camera, print_commands_and_indicators, print_construction_info, print_fleet_cargo,
+  print_map_stats, print_robots_needing_help, print_selected_robot,
+};
+use crate::utils::front_end::FrontEnd;
 use crate::utils::keyboard::handle_keyboard_events;
+use crate::utils::locale::Msg;
+use crate::utils::overlay::{Overlay, compute_overlay};
+use crate::utils::stats_view::draw_stats;
+use crate::utils::terminal::{TerminalGuard, handle_signals, interrupted};
+use crate::utils::theme::Theme;
 use clearscreen;
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use crossterm::terminal::{self, disable_raw_mode, enable_raw_mode};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use std::io::{Stdout, stdout};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+// Fréquence d'affichage, indépendante de la vitesse de simulation
+const RENDER_FPS: u64 = 10;
+
+fn render(state: &GameState, front: &FrontEnd, automation_enabled: bool) -> Result<(), GameError> {
+  disable_raw_mode().ok();
+  clearscreen::clear()?;
+  let theme = &front.theme;
+
+  let background = compute_overlay(state, front.overlay, theme);
+  let view = MapView {
+    camera: &front.camera,
+    theme,
+    background: background.as_ref(),
+  };
+  state.map.print_map(
+    &state.robots,
+    &state.stations,
+    state.resources_revealed,
+    front.selected_robot,
+    &view,
+  );
+  state
+    .map
+    .print_minimap(&state.robots, &state.stations, &view);
+  print_camera(&front.camera, &state.map, theme);
+  if front.overlay != Overlay::None {
+    println!("{}{}", theme.icon("🗺️"), Msg::OverlayLine(front.overlay));
+  }
+  print_commands_and_indicators(theme);
+  print_map_stats(&state.map, &state.clock, &state.stations, theme);
+  print_construction_info(
+    &state.construction_queue,
+    &suggest_shortcuts(&state.map, &state.stations, state.resources_revealed, 1),
+    theme,
+  );
+
+  println!("{}", Msg::Automation(automation_enabled));
+  print_selected_robot(
+    front.selected_robot,
+    front.selected(&state.robots),
+    &state.clock,
+    theme,
+  );
+  print_fleet_cargo(&state.robots, theme);
+  print_robots_needing_help(&state.robots, theme);
+
+  if let Some(msg) = &state.last_collect_message {
+    println!("{msg}");
+  }
+
+  if front.console.open {
+    println!(":{}_", front.console.input);
+  }
 
-pub fn run_game_loop(mut state: GameState) -> Result<(), Box<dyn std::error::Error>> {
   enable_raw_mode()?;
-  let mut automation_enabled = true;
+  Ok(())
+}
 
-  loop {
-    disable_raw_mode().ok();
-    clearscreen::clear()?;
+// Cadre la vue sur le terminal et, en mode suivi, sur le robot sélectionné
+fn update_camera(state: &GameState, front: &mut FrontEnd) {
+  if let Ok((columns, rows)) = terminal::size() {
+    front.camera.resize(columns, rows, &state.map);
+  }
+  if front.camera.follow
+    && let Some((x, y)) = front.selected(&state.robots).map(|r| (r.x, r.y))
+  {
+    front.camera.center_on(x, y, &state.map);
+  }
+}
 
-    state
-      .map
-      .print_map(&state.robots, &state.station, state.resources_revealed);
-    print_commands_and_indicators();
-    print_map_stats(&state.map, state.robot_speed_ms, &state.station);
+// Onglet statistiques dessiné avec ratatui, vue principale en texte
+fn render_view(
+  terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+  state: &GameState,
+  front: &FrontEnd,
+  automation_enabled: bool,
+  stats_shown: &mut bool,
+) -> Result<(), GameError> {
+  if !front.show_stats {
+    *stats_shown = false;
+    return render(state, front, automation_enabled);
+  }
 
-    println!(
-      "Automation: {}",
-      if automation_enabled { "ON" } else { "OFF" }
-    );
+  // Le tampon de ratatui ne connaît pas ce que la vue texte a affiché
+  if !*stats_shown {
+    terminal.clear()?;
+    *stats_shown = true;
+  }
+  terminal.draw(|frame| draw_stats(frame, state, &front.theme))?;
+  Ok(())
+}
 
-    if let Some(msg) = &state.last_collect_message {
-      println!("{msg}");
-    }
+// `autosave` : fichier où sauvegarder la partie si elle est interrompue
+pub fn run_game_loop(
+  mut sim: Simulation,
+  theme: Theme,
+  autosave: Option<&Path>,
+) -> Result<(), GameError> {
+  handle_signals()?;
+  let guard = TerminalGuard::enter()?;
 
-    enable_raw_mode()?;
+  let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+  let mut front = FrontEnd::new(theme);
+  let mut stats_shown = false;
 
-    if handle_keyboard_events(&mut state, &mut automation_enabled)? {
-      break; // Quitte la boucle principale si Esc pressé
-    }
+  let frame = Duration::from_millis(1000 / RENDER_FPS);
+  let mut last_update = Instant::now();
+
+  'game: loop {
+    update_camera(&sim.state, &mut front);
+    render_view(
+      &mut terminal,
+      &sim.state,
+      &front,
+      sim.automation,
+      &mut stats_shown,
+    )?;
 
-    automate_all_robots(&mut state, automation_enabled);
+    // Traite les événements au fil de l'eau jusqu'à la prochaine image
+    let next_frame = Instant::now() + frame;
+    loop {
+      if interrupted() {
+        break 'game;
+      }
+      let now = Instant::now();
+      if now >= next_frame {
+        break;
+      }
+      if handle_keyboard_events(
+        &mut sim.state,
+        &mut front,
+        &mut sim.automation,
+        next_frame - now,
+      )? {
+        break 'game; // Quitte la boucle principale si Esc pressé
+      }
+    }
 
-    std::thread::sleep(std::time::Duration::from_millis(state.robot_speed_ms));
+    // Rattrape le nombre de tours dus depuis la dernière image, dans la limite d'une image
+    let now = Instant::now();
+    let ticks = sim.state.clock.ticks_due(now - last_update);
+    last_update = now;
+    for _ in 0..ticks {
+      sim.step();
+      if last_update.elapsed() >= frame {
+        break;
+      }
+    }
+    // Le jeu affiche les messages de l'état, les événements ne servent qu'aux outils
+    sim.events();
   }
 
+  drop(guard);
+  if interrupted() {
+    println!("{}", Msg::Interrupted);
+    if let Some(path) = autosave {
+      save_game(&sim.state, path)?;
+      println!("{}", Msg::GameSaved(&path.display().to_string()));
+    }
+  }
+  println!("{}", Msg::Quit);
   Ok(())
 }
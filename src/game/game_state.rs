@@ -1,60 +1,90 @@
-use std::collections::HashMap;
+use std::collections::VecDeque;
 
 use crate::Config;
+use crate::GameError;
+use crate::game::behaviour::BehaviourRegistry;
+use crate::game::clock::SimClock;
+use crate::game::events::SimEvent;
+use crate::game::stats::Stats;
+use crate::map::cell::Cell;
 use crate::robot::robot::{Robot, RobotType};
 use crate::{Map, Station};
 
 pub struct GameState {
   pub robots: Vec<Robot>,
   pub map: Map,
-  pub station: Station,
+  pub stations: Vec<Station>,
   pub last_collect_message: Option<String>,
   pub resources_revealed: bool,
-  pub robot_speed_ms: u64,
+  pub clock: SimClock,
+  pub construction_queue: VecDeque<(usize, usize)>,
+  pub seed: u32,
+  pub stats: Stats,
+  // Vidés par `Simulation::events`
+  pub events: Vec<SimEvent>,
+  pub behaviours: BehaviourRegistry,
 }
 
+// La station principale est posée en (9, 4), la carte doit la contenir
+pub const MIN_MAP_SIZE: (usize, usize) = (12, 8);
+// Case de la station principale, d'où partent tous les robots
+pub const START: (usize, usize) = (9, 4);
+
 impl GameState {
-  pub fn new(config: &Config) -> Self {
+  pub fn try_new(config: &Config) -> Result<Self, GameError> {
+    if config.width < MIN_MAP_SIZE.0 || config.height < MIN_MAP_SIZE.1 {
+      return Err(GameError::MapTooSmall {
+        width: config.width,
+        height: config.height,
+        min: MIN_MAP_SIZE,
+      });
+    }
+
     let num_collectors = 5;
     let num_explorators = 1;
+    let (x, y) = START;
 
     let mut robots = Vec::new();
 
     for _i in 0..num_explorators {
-      robots.push(Robot {
-        x: 7,
-        y: 4,
-        inventory: HashMap::new(),
-        inventory_capacity: 5,
-        collected_science_positions: Vec::new(),
-        robots: Vec::new(),
-        robot_type: RobotType::Explorator,
-      });
+      robots.push(Robot::new(RobotType::Explorator, x, y));
     }
 
-    for i in 0..num_collectors {
-      robots.push(Robot {
-        x: if i == 0 { 8 } else { 7 },
-        y: 4,
-        inventory: HashMap::new(),
-        inventory_capacity: 5,
-        collected_science_positions: Vec::new(),
-        robots: Vec::new(),
-        robot_type: RobotType::Collector,
-      });
+    for _i in 0..num_collectors {
+      robots.push(Robot::new(RobotType::Collector, x, y));
     }
 
-    Self {
+    robots.push(Robot::new(RobotType::Builder, x, y));
+
+    // Le bruit a pu recouvrir la station d'un obstacle
+    let mut map = Map::try_new(config.width, config.height, config.seed)?;
+    if matches!(map.grid()[START], Cell::Wall | Cell::Obstacle) {
+      map.set_cell(x, y, Cell::Empty);
+    }
+
+    Ok(Self {
       robots,
-      map: Map::new(config.width, config.height, config.seed),
-      station: Station {
-        x: 9,
-        y: 4,
-        inventory: HashMap::new(),
-      },
+      map,
+      stations: vec![Station::new(x, y)],
       last_collect_message: None,
       resources_revealed: false,
-      robot_speed_ms: 251,
-    }
+      clock: SimClock::new(250),
+      construction_queue: VecDeque::new(),
+      seed: config.seed,
+      stats: Stats::default(),
+      events: Vec::new(),
+      behaviours: BehaviourRegistry::default(),
+    })
+  }
+
+  // Positions de tous les robots sauf celui d'indice `index`
+  pub fn other_robot_positions(&self, index: usize) -> Vec<(usize, usize)> {
+    self
+      .robots
+      .iter()
+      .enumerate()
+      .filter(|(j, _)| *j != index)
+      .map(|(_, r)| (r.x, r.y))
+      .collect()
   }
 }
@@ -0,0 +1,276 @@
+use crate::GameState;
+use crate::map::cell::Cell;
+use crate::robot::robot::ResourceType;
+use crate::utils::locale::Msg;
+use crate::utils::theme::Theme;
+use std::collections::HashMap;
+use std::fmt;
+
+const RESOURCES: [(ResourceType, Cell); 3] = [
+  (ResourceType::Mineral, Cell::Mineral),
+  (ResourceType::Energy, Cell::Energy),
+  (ResourceType::Science, Cell::Science),
+];
+
+// Règle enfreinte à la fin d'un tour ; les robots sont numérotés à partir de 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+  ResourcesChanged {
+    resource: ResourceType,
+    before: u32,
+    after: u32,
+  },
+  RobotsStacked {
+    robots: (usize, usize),
+    x: usize,
+    y: usize,
+  },
+  RobotBlocked {
+    robot: usize,
+    x: usize,
+    y: usize,
+  },
+  OverCapacity {
+    robot: usize,
+    load: u32,
+    capacity: u32,
+  },
+  ZoneCount {
+    zone: usize,
+    stored: (u32, u32),
+    counted: (u32, u32),
+  },
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Violation::ResourcesChanged {
+        ref resource,
+        before,
+        after,
+      } => write!(
+        f,
+        "{}",
+        Msg::ResourcesNotConserved(&Msg::Resource(resource).to_string(), before, after)
+      ),
+      Violation::RobotsStacked {
+        robots: (a, b),
+        x,
+        y,
+      } => write!(f, "{}", Msg::RobotsStacked(a + 1, b + 1, x, y)),
+      Violation::RobotBlocked { robot, x, y } => {
+        write!(f, "{}", Msg::RobotOnBlockedCell(robot + 1, x, y))
+      }
+      Violation::OverCapacity {
+        robot,
+        load,
+        capacity,
+      } => write!(f, "{}", Msg::RobotOverCapacity(robot + 1, load, capacity)),
+      Violation::ZoneCount {
+        zone,
+        stored,
+        counted,
+      } => write!(f, "{}", Msg::ZoneCountMismatch(zone, stored, counted)),
+    }
+  }
+}
+
+// Minerai, énergie et science présents sur la carte, dans les robots et dans les stations,
+// en comptant ce que les constructions ont consommé
+pub fn resource_totals(state: &GameState) -> [u32; 3] {
+  let mut totals = [0; 3];
+  for (total, (kind, cell)) in totals.iter_mut().zip(&RESOURCES) {
+    *total += state
+      .map
+      .grid()
+      .cells()
+      .iter()
+      .filter(|&c| c == cell)
+      .count() as u32;
+    for robot in &state.robots {
+      *total += robot.inventory.get(kind).copied().unwrap_or(0);
+    }
+    for station in &state.stations {
+      *total += station.amount(kind) + station.consumed.get(kind).copied().unwrap_or(0);
+    }
+  }
+  totals
+}
+
+// Vérifie l'état à la fin d'un tour ; `before` vient de `resource_totals` au début du tour
+pub fn check_invariants(state: &GameState, before: [u32; 3]) -> Vec<Violation> {
+  let mut violations = Vec::new();
+
+  let after = resource_totals(state);
+  for ((resource, _), (&before, &after)) in RESOURCES.iter().zip(before.iter().zip(&after)) {
+    if before != after {
+      violations.push(Violation::ResourcesChanged {
+        resource: resource.clone(),
+        before,
+        after,
+      });
+    }
+  }
+
+  let mut occupied: HashMap<(usize, usize), usize> = HashMap::new();
+  for (i, robot) in state.robots.iter().enumerate() {
+    let (x, y) = (robot.x, robot.y);
+    let on_station = state.stations.iter().any(|s| (s.x, s.y) == (x, y));
+    if let Some(&other) = occupied.get(&(x, y))
+      && !on_station
+    {
+      violations.push(Violation::RobotsStacked {
+        robots: (other, i),
+        x,
+        y,
+      });
+    }
+    occupied.entry((x, y)).or_insert(i);
+
+    let passable = x < state.map.width
+      && y < state.map.height
+      && !matches!(state.map.grid()[(x, y)], Cell::Wall | Cell::Obstacle);
+    if !passable {
+      violations.push(Violation::RobotBlocked { robot: i, x, y });
+    }
+
+    if robot.load() > robot.hold.capacity {
+      violations.push(Violation::OverCapacity {
+        robot: i,
+        load: robot.load(),
+        capacity: robot.hold.capacity,
+      });
+    }
+  }
+
+  let counted = state.map.count_resources_in_zones();
+  for (zone, counted) in state.map.zones.iter().zip(counted) {
+    if zone.resource_count != counted {
+      violations.push(Violation::ZoneCount {
+        zone: zone.id,
+        stored: zone.resource_count,
+        counted,
+      });
+    }
+  }
+
+  violations
+}
+
+fn inventory(inventory: &HashMap<ResourceType, u32>) -> String {
+  RESOURCES
+    .iter()
+    .map(|(kind, _)| {
+      let qty = inventory.get(kind).copied().unwrap_or(0);
+      format!("{} {qty}", Msg::Resource(kind))
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+// Rapport affiché quand une règle est enfreinte : les violations puis l'état des robots,
+// des stations et des zones
+pub fn diagnostic_dump(state: &GameState, violations: &[Violation], theme: &Theme) -> String {
+  let mut dump = format!("{}\n", Msg::InvariantsBroken(state.stats.tick, state.seed));
+  for violation in violations {
+    dump += &format!("  - {violation}\n");
+  }
+
+  for (i, robot) in state.robots.iter().enumerate() {
+    dump += &format!(
+      "  #{} {} ({}, {}) {}/{} [{}] {}\n",
+      i + 1,
+      Msg::RobotKind(robot.robot_type),
+      robot.x,
+      robot.y,
+      robot.load(),
+      robot.hold.capacity,
+      inventory(&robot.inventory),
+      robot.task
+    );
+  }
+  for (i, station) in state.stations.iter().enumerate() {
+    dump += &format!(
+      "  S{i} ({}, {}) [{}] -[{}]\n",
+      station.x,
+      station.y,
+      inventory(&station.inventory),
+      inventory(&station.consumed)
+    );
+  }
+  for (zone, counted) in state
+    .map
+    .zones
+    .iter()
+    .zip(state.map.count_resources_in_zones())
+  {
+    let unlocked = if zone.is_unlocked {
+      format!(" {}", theme.check_mark())
+    } else {
+      String::new()
+    };
+    dump += &format!(
+      "  {} {:?} {:?}{unlocked}\n",
+      zone.name, zone.resource_count, counted
+    );
+  }
+  dump
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Config;
+
+  fn state() -> GameState {
+    GameState::try_new(&Config::default()).unwrap()
+  }
+
+  #[test]
+  fn a_fresh_game_respects_every_invariant() {
+    let state = state();
+    assert_eq!(check_invariants(&state, resource_totals(&state)), vec![]);
+  }
+
+  #[test]
+  fn broken_rules_are_reported() {
+    let mut state = state();
+    let before = resource_totals(&state);
+    // Les robots partent tous de la station, où ils peuvent s'empiler
+    let (x, y) = (state.robots[1].x - 1, state.robots[1].y);
+    state.robots[1].x = x;
+    state.robots[2].x = x;
+    state.robots[3].x = 0;
+    state.robots[3].y = 0;
+    state.robots[4].inventory.insert(ResourceType::Energy, 6);
+
+    let violations = check_invariants(&state, before);
+    assert!(violations.contains(&Violation::ResourcesChanged {
+      resource: ResourceType::Energy,
+      before: before[1],
+      after: before[1] + 6,
+    }));
+    assert!(violations.contains(&Violation::RobotsStacked {
+      robots: (1, 2),
+      x,
+      y,
+    }));
+    assert!(violations.contains(&Violation::RobotBlocked {
+      robot: 3,
+      x: 0,
+      y: 0,
+    }));
+    assert!(violations.contains(&Violation::OverCapacity {
+      robot: 4,
+      load: 6,
+      capacity: 5,
+    }));
+
+    let dump = diagnostic_dump(&state, &violations, &Theme::default());
+    assert_eq!(
+      dump.lines().count(),
+      1 + violations.len() + 7 + 1 + state.map.zones.len()
+    );
+  }
+}
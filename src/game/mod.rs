@@ -1,3 +1,14 @@
+pub mod behaviour;
+pub mod clock;
+pub mod command;
+pub mod construction;
+pub mod events;
 pub mod game_automation;
 pub mod game_loop;
-pub mod game_state;
\ No newline at end of file
+pub mod game_state;
+pub mod invariants;
+pub mod outposts;
+pub mod save;
+pub mod script;
+pub mod simulation;
+pub mod stats;
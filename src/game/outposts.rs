@@ -0,0 +1,308 @@
+use crate::error::{OutpostError, TransferError};
+use crate::game::construction::{collector_targets, payback_steps};
+use crate::game::game_automation::distance_field;
+use crate::map::cell::Cell;
+use crate::map::grid::Grid;
+use crate::map::map::Map;
+use crate::robot::robot::ResourceType;
+use crate::station::station::Station;
+use std::collections::{HashMap, VecDeque};
+
+// Coût d'un avant-poste, prélevé dans les stations les plus proches du chantier
+pub const OUTPOST_MINERAL_COST: u32 = 10;
+pub const OUTPOST_ENERGY_COST: u32 = 5;
+// Rayon (en pas) des ressources qu'un avant-poste est censé desservir
+pub const OUTPOST_RADIUS: u32 = 8;
+// Nombre de tours entre deux recherches d'emplacement par l'automatisation
+pub const OUTPOST_SEARCH_TICKS: u64 = 20;
+
+// Stock cumulé de toutes les stations
+pub fn total_amount(stations: &[Station], res: &ResourceType) -> u32 {
+  stations.iter().map(|s| s.amount(res)).sum()
+}
+
+// Dépense `qty` unités pour une construction en puisant d'abord dans les stations les
+// plus proches de `near`, ou ne fait rien si toutes les stations réunies n'en ont pas assez
+pub fn consume_nearest(
+  stations: &mut [Station],
+  near: (usize, usize),
+  res: ResourceType,
+  qty: u32,
+) -> bool {
+  if total_amount(stations, &res) < qty {
+    return false;
+  }
+  let mut order: Vec<usize> = (0..stations.len()).collect();
+  order.sort_by_key(|&i| stations[i].x.abs_diff(near.0) + stations[i].y.abs_diff(near.1));
+
+  let mut left = qty;
+  for i in order {
+    let part = left.min(stations[i].amount(&res));
+    if part > 0 {
+      stations[i].consume(res.clone(), part);
+      left -= part;
+    }
+  }
+  true
+}
+
+pub fn can_afford_outpost(stations: &[Station]) -> bool {
+  total_amount(stations, &ResourceType::Mineral) >= OUTPOST_MINERAL_COST
+    && total_amount(stations, &ResourceType::Energy) >= OUTPOST_ENERGY_COST
+}
+
+pub fn can_host_outpost(map: &Map, stations: &[Station], x: usize, y: usize) -> bool {
+  !map.is_border(x, y)
+    && matches!(map.grid()[(x, y)], Cell::Empty | Cell::Road)
+    && !stations.iter().any(|s| s.x == x && s.y == y)
+}
+
+// Renvoie le numéro de la nouvelle station
+pub fn build_outpost(
+  map: &Map,
+  stations: &mut Vec<Station>,
+  x: usize,
+  y: usize,
+) -> Result<usize, OutpostError> {
+  if !can_host_outpost(map, stations, x, y) {
+    return Err(OutpostError::NotBuildable { x, y });
+  }
+  if !can_afford_outpost(stations) {
+    return Err(OutpostError::TooExpensive);
+  }
+
+  consume_nearest(
+    stations,
+    (x, y),
+    ResourceType::Mineral,
+    OUTPOST_MINERAL_COST,
+  );
+  consume_nearest(stations, (x, y), ResourceType::Energy, OUTPOST_ENERGY_COST);
+  stations.push(Station::new(x, y));
+  Ok(stations.len() - 1)
+}
+
+// Déplace `qty` unités d'une station à une autre
+pub fn transfer(
+  stations: &mut [Station],
+  from: usize,
+  to: usize,
+  res: ResourceType,
+  qty: u32,
+) -> Result<(), TransferError> {
+  if let Some(&missing) = [from, to].iter().find(|&&i| i >= stations.len()) {
+    return Err(TransferError::NoSuchStation(missing));
+  }
+  if from == to {
+    return Err(TransferError::SameStation);
+  }
+  if !stations[from].spend(res.clone(), qty) {
+    return Err(TransferError::NotEnough {
+      station: from,
+      available: stations[from].amount(&res),
+    });
+  }
+  *stations[to].inventory.entry(res).or_insert(0) += qty;
+  Ok(())
+}
+
+// Rapatrie tout le stock des avant-postes vers la station principale
+pub fn consolidate_to_main(stations: &mut [Station]) {
+  for i in 1..stations.len() {
+    let stock: Vec<(ResourceType, u32)> = stations[i]
+      .inventory
+      .iter()
+      .map(|(res, qty)| (res.clone(), *qty))
+      .collect();
+    for (res, qty) in stock {
+      // Le stock vient d'être relevé, il suffit forcément
+      let _ = transfer(stations, i, 0, res, qty);
+    }
+  }
+}
+
+// Cases atteignables en au plus `radius` pas depuis `from`, avec leur distance
+fn reachable_within(
+  map: &Map,
+  from: (usize, usize),
+  radius: u32,
+  resources_revealed: bool,
+) -> HashMap<usize, u32> {
+  let start = map.grid().index_of(from.0, from.1);
+  let mut dist = HashMap::from([(start, 0)]);
+  let mut queue = VecDeque::from([start]);
+
+  while let Some(i) = queue.pop_front() {
+    let d = dist[&i];
+    if d == radius {
+      continue;
+    }
+    for next in map.grid().neighbour_indices(i) {
+      if !dist.contains_key(&next) && !map.blocks(next, resources_revealed) {
+        dist.insert(next, d + 1);
+        queue.push_back(next);
+      }
+    }
+  }
+
+  dist
+}
+
+// Meilleur emplacement pour un nouvel avant-poste et nombre de pas qu'il ferait
+// gagner : pour chaque ressource à moins de `OUTPOST_RADIUS` pas, l'aller-retour passe
+// de la distance à la station la plus proche à la distance à l'avant-poste.
+// L'emplacement n'est retenu que si ce gain dépasse le trajet qu'il a fallu pour
+// récolter son coût, estimé avec l'aller-retour moyen vers les ressources connues.
+pub fn suggest_outpost_site(
+  map: &Map,
+  stations: &[Station],
+  resources_revealed: bool,
+) -> Option<((usize, usize), u32)> {
+  let sources: Vec<(usize, usize)> = stations.iter().map(|s| (s.x, s.y)).collect();
+  let dist = distance_field(map, &sources, resources_revealed);
+
+  let resources = collector_targets(map, &dist, resources_revealed);
+  if resources.is_empty() {
+    return None;
+  }
+
+  // Les chemins se parcourent dans les deux sens : on part de chaque ressource
+  let mut gains = Grid::new(map.width, map.height, 0u32);
+  for &(rx, ry, rd) in &resources {
+    for (i, local) in reachable_within(map, (rx, ry), OUTPOST_RADIUS, resources_revealed) {
+      if local < rd {
+        gains.cells_mut()[i] += 2 * (rd - local);
+      }
+    }
+  }
+
+  let mut best: Option<((usize, usize), u32)> = None;
+  for ((x, y), &gain) in gains.iter() {
+    if gain > 0 && can_host_outpost(map, stations, x, y) && best.is_none_or(|(_, g)| gain > g) {
+      best = Some(((x, y), gain));
+    }
+  }
+
+  let payback = payback_steps(&resources, OUTPOST_MINERAL_COST + OUTPOST_ENERGY_COST);
+  best.filter(|(_, gain)| *gain > payback)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stocked(x: usize, y: usize, mineral: u32) -> Station {
+    let mut station = Station::new(x, y);
+    station.inventory.insert(ResourceType::Mineral, mineral);
+    station
+  }
+
+  #[test]
+  fn construction_spends_from_the_nearest_stations_first() {
+    let mut stations = vec![stocked(1, 1, 4), stocked(10, 10, 3)];
+    assert!(consume_nearest(
+      &mut stations,
+      (9, 9),
+      ResourceType::Mineral,
+      5
+    ));
+    assert_eq!(stations[1].amount(&ResourceType::Mineral), 0);
+    assert_eq!(stations[0].amount(&ResourceType::Mineral), 2);
+    assert_eq!(stations[0].consumed[&ResourceType::Mineral], 2);
+    assert_eq!(stations[1].consumed[&ResourceType::Mineral], 3);
+
+    assert!(!consume_nearest(
+      &mut stations,
+      (1, 1),
+      ResourceType::Mineral,
+      3
+    ));
+    assert_eq!(stations[0].amount(&ResourceType::Mineral), 2);
+  }
+
+  fn stocked_for_outpost(x: usize, y: usize) -> Station {
+    let mut station = Station::new(x, y);
+    station
+      .inventory
+      .insert(ResourceType::Mineral, OUTPOST_MINERAL_COST);
+    station
+      .inventory
+      .insert(ResourceType::Energy, OUTPOST_ENERGY_COST);
+    station
+  }
+
+  #[test]
+  fn an_outpost_needs_a_free_cell_and_its_cost() {
+    let map = Map::from_rows(&["OOOOOO", "O..O.O", "OOOOOO"]);
+    let mut stations = vec![stocked_for_outpost(1, 1)];
+    for (x, y) in [(3, 1), (1, 1), (0, 1)] {
+      assert_eq!(
+        build_outpost(&map, &mut stations, x, y),
+        Err(OutpostError::NotBuildable { x, y })
+      );
+    }
+
+    assert_eq!(build_outpost(&map, &mut stations, 4, 1), Ok(1));
+    assert_eq!((stations.len(), stations[1].x, stations[1].y), (2, 4, 1));
+    assert_eq!(stations[0].amount(&ResourceType::Mineral), 0);
+    assert_eq!(
+      stations[0].consumed[&ResourceType::Energy],
+      OUTPOST_ENERGY_COST
+    );
+
+    assert_eq!(
+      build_outpost(&map, &mut stations, 2, 1),
+      Err(OutpostError::TooExpensive)
+    );
+    assert_eq!(stations.len(), 2);
+  }
+
+  #[test]
+  fn outposts_are_suggested_only_where_they_pay_back() {
+    // Un long couloir qui mène à un gisement éloigné de la station
+    let wall = "O".repeat(70);
+    let deposit = format!("O{}{}O", ".".repeat(50), "M".repeat(18));
+    let corridor = format!("O{}O", ".".repeat(68));
+    let map = Map::from_rows(&[&wall, &deposit, &corridor, &deposit, &wall]);
+    let stations = [Station::new(1, 2)];
+    let ((x, y), gain) = suggest_outpost_site(&map, &stations, true).unwrap();
+    assert_eq!(y, 2);
+    assert!((51..=68).contains(&x), "{x}");
+    assert!(gain > 0);
+
+    // Des ressources au pied de la station ne justifient pas la dépense
+    let near = Map::from_rows(&["OOOOOO", "O.MM.O", "OOOOOO"]);
+    assert_eq!(
+      suggest_outpost_site(&near, &[Station::new(1, 1)], true),
+      None
+    );
+  }
+
+  #[test]
+  fn stocks_move_between_existing_stations_only() {
+    let mut stations = vec![stocked(1, 1, 4), Station::new(5, 5)];
+    assert_eq!(
+      transfer(&mut stations, 0, 1, ResourceType::Mineral, 3),
+      Ok(())
+    );
+    assert_eq!(stations[0].amount(&ResourceType::Mineral), 1);
+    assert_eq!(stations[1].amount(&ResourceType::Mineral), 3);
+
+    assert_eq!(
+      transfer(&mut stations, 0, 1, ResourceType::Mineral, 2),
+      Err(TransferError::NotEnough {
+        station: 0,
+        available: 1
+      })
+    );
+    assert_eq!(
+      transfer(&mut stations, 1, 2, ResourceType::Mineral, 1),
+      Err(TransferError::NoSuchStation(2))
+    );
+    assert_eq!(
+      transfer(&mut stations, 1, 1, ResourceType::Mineral, 1),
+      Err(TransferError::SameStation)
+    );
+    assert_eq!(stations[1].amount(&ResourceType::Mineral), 3);
+  }
+}
@@ -0,0 +1,97 @@
+use crate::GameState;
+use crate::error::GameError;
+use crate::map::cell::Cell;
+use crate::robot::robot::{ResourceType, RobotType};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub fn cell_to_char(cell: Cell) -> char {
+  match cell {
+    Cell::Wall => '#',
+    Cell::Obstacle => 'O',
+    Cell::Empty => '.',
+    Cell::Energy => 'E',
+    Cell::Mineral => 'M',
+    Cell::Science => 'S',
+    Cell::Road => 'R',
+  }
+}
+
+pub fn robot_type_name(robot_type: RobotType) -> &'static str {
+  match robot_type {
+    RobotType::Explorator => "explorator",
+    RobotType::Collector => "collector",
+    RobotType::Builder => "builder",
+  }
+}
+
+fn stock(inventory: &HashMap<ResourceType, u32>) -> String {
+  [
+    ResourceType::Mineral,
+    ResourceType::Energy,
+    ResourceType::Science,
+  ]
+  .iter()
+  .map(|res| inventory.get(res).copied().unwrap_or(0).to_string())
+  .collect::<Vec<_>>()
+  .join(" ")
+}
+
+// Sauvegarde texte : une ligne par élément, la grille ligne par ligne
+pub fn save_game(state: &GameState, path: &Path) -> Result<(), GameError> {
+  write_save(state, path).map_err(|source| GameError::Save {
+    path: path.into(),
+    source,
+  })
+}
+
+fn write_save(state: &GameState, path: &Path) -> io::Result<()> {
+  let mut out = BufWriter::new(File::create(path)?);
+
+  writeln!(out, "seed {}", state.seed)?;
+  writeln!(out, "size {} {}", state.map.width, state.map.height)?;
+  writeln!(out, "turn {}", state.map.current_turn)?;
+  writeln!(out, "revealed {}", state.resources_revealed as u8)?;
+  writeln!(out, "speed {}", state.clock.base_tick_ms)?;
+
+  for row in state.map.grid().rows() {
+    let line: String = row.iter().map(|c| cell_to_char(*c)).collect();
+    writeln!(out, "row {line}")?;
+  }
+
+  for zone in &state.map.zones {
+    writeln!(out, "zone {} {}", zone.id, zone.is_unlocked as u8)?;
+  }
+
+  for station in &state.stations {
+    writeln!(
+      out,
+      "station {} {} {}",
+      station.x,
+      station.y,
+      stock(&station.inventory)
+    )?;
+  }
+
+  for robot in &state.robots {
+    writeln!(
+      out,
+      "robot {} {} {} {} {} {} {}",
+      robot_type_name(robot.robot_type),
+      robot.x,
+      robot.y,
+      robot.hold.capacity,
+      robot.automated as u8,
+      robot.speed,
+      stock(&robot.inventory)
+    )?;
+  }
+
+  for &(x, y) in &state.construction_queue {
+    writeln!(out, "build {x} {y}")?;
+  }
+
+  out.flush()
+}
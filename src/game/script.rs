@@ -0,0 +1,354 @@
+use crate::game::behaviour::{
+  Action, RobotBehaviour, RobotView, WorldView, return_to_nearest_station,
+};
+use crate::game::game_automation::next_step_towards;
+use crate::map::cell::Cell;
+use crate::map::map::Map;
+use crate::robot::robot::{ResourceType, RobotTask};
+use crate::utils::locale::Msg;
+use rhai::{AST, Dynamic, Engine, EvalAltResult, Scope};
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::SystemTime;
+
+// Opérations autorisées par tour à chaque robot piloté par un script
+pub const OPERATIONS_PER_TICK: u64 = 200_000;
+// Rayon des cases visibles autour du robot
+pub const NEARBY_RADIUS: usize = 2;
+// Ressources connues transmises au script, les plus proches d'abord
+pub const KNOWN_RESOURCES_LIMIT: usize = 20;
+
+// Action renvoyée par la fonction `decide` du script. Comme pour les stratégies intégrées,
+// la collecte et le déchargement se font d'eux-mêmes en fin de tour : `Collect` garde le
+// robot sur sa case pour qu'il ramasse la ressource qui s'y trouve, `Unload` le ramène
+// vers la station la plus proche où il vide sa soute en arrivant
+#[derive(Debug, Clone)]
+enum ScriptAction {
+  Move(i64, i64),
+  GoTo(i64, i64),
+  Collect,
+  Unload,
+  Wait,
+}
+
+// Ressource visible : position et nom transmis au script
+type KnownResource = (usize, usize, &'static str);
+
+// Les robots planifient en parallèle : chaque fil tient le compte de l'appel en cours
+thread_local! {
+  static ALLOWED: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+  static USED: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+struct Compiled {
+  ast: AST,
+  modified: Option<SystemTime>,
+}
+
+// Stratégie écrite en Rhai : le fichier définit `fn decide(robot, world)`
+// et il est rechargé dès qu'il change sur le disque
+pub struct ScriptBehaviour {
+  name: String,
+  path: PathBuf,
+  engine: Engine,
+  compiled: RwLock<Option<Compiled>>,
+  // Ressources visibles, relevées une fois par tour pour tous les robots
+  known: RwLock<Vec<KnownResource>>,
+  // Opérations déjà consommées ce tour, par robot
+  spent: Mutex<HashMap<usize, u64>>,
+  // Erreurs du tour, avec le robot concerné (aucun pour la compilation)
+  errors: Mutex<Vec<(Option<usize>, String)>>,
+  reported: Mutex<Vec<String>>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+  std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn cell_name(cell: Cell, visible: bool) -> &'static str {
+  match cell {
+    Cell::Wall => "wall",
+    Cell::Obstacle => "obstacle",
+    Cell::Empty => "empty",
+    Cell::Road => "road",
+    Cell::Science => "science",
+    Cell::Mineral | Cell::Energy if !visible => "unknown",
+    Cell::Mineral => "mineral",
+    Cell::Energy => "energy",
+  }
+}
+
+fn position(x: usize, y: usize) -> rhai::Map {
+  let mut map = rhai::Map::new();
+  map.insert("x".into(), (x as i64).into());
+  map.insert("y".into(), (y as i64).into());
+  map
+}
+
+fn robot_map(robot: &RobotView) -> rhai::Map {
+  let amount = |res| robot.inventory.get(&res).copied().unwrap_or(0) as i64;
+  let mut inventory = rhai::Map::new();
+  inventory.insert("mineral".into(), amount(ResourceType::Mineral).into());
+  inventory.insert("energy".into(), amount(ResourceType::Energy).into());
+  inventory.insert("science".into(), amount(ResourceType::Science).into());
+
+  let mut map = position(robot.x, robot.y);
+  map.insert("index".into(), (robot.index as i64).into());
+  map.insert(
+    "kind".into(),
+    format!("{:?}", robot.robot_type).to_lowercase().into(),
+  );
+  map.insert("inventory".into(), inventory.into());
+  map.insert("count".into(), (robot.inventory_count() as i64).into());
+  map.insert("load".into(), (robot.load as i64).into());
+  map.insert("capacity".into(), (robot.capacity as i64).into());
+  map.insert("full".into(), robot.full.into());
+  map
+}
+
+// Ressources visibles de la carte, dans l'ordre des lignes
+fn known_resources(map: &Map, resources_revealed: bool) -> Vec<KnownResource> {
+  map
+    .grid()
+    .iter()
+    .filter_map(|((x, y), &cell)| match cell {
+      Cell::Mineral | Cell::Energy | Cell::Science
+        if resources_revealed || map.is_resource_accessible(x, y) =>
+      {
+        Some((x, y, cell_name(cell, true)))
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+// Vue du monde en lecture seule : une copie, le script ne peut rien modifier
+fn world_map(robot: &RobotView, world: &WorldView, known: &[KnownResource]) -> rhai::Map {
+  let map = world.map;
+  let visible = |x, y| world.resources_revealed || map.is_resource_accessible(x, y);
+
+  // Lignes de cases autour du robot, hors carte comprise comme un mur
+  let nearby: rhai::Array = (0..=2 * NEARBY_RADIUS)
+    .map(|dy| {
+      let row: rhai::Array = (0..=2 * NEARBY_RADIUS)
+        .map(|dx| {
+          let x = (robot.x + dx).checked_sub(NEARBY_RADIUS);
+          let y = (robot.y + dy).checked_sub(NEARBY_RADIUS);
+          let name = match (x, y) {
+            (Some(x), Some(y)) => map
+              .grid()
+              .get(x, y)
+              .map_or("wall", |&cell| cell_name(cell, visible(x, y))),
+            _ => "wall",
+          };
+          Dynamic::from(name)
+        })
+        .collect();
+      row.into()
+    })
+    .collect();
+
+  // Seules les plus proches sont triées ; à distance égale, dans l'ordre des lignes
+  let by_distance = |&(x, y, _): &KnownResource| (x.abs_diff(robot.x) + y.abs_diff(robot.y), y, x);
+  let mut closest = known.to_vec();
+  if closest.len() > KNOWN_RESOURCES_LIMIT {
+    closest.select_nth_unstable_by_key(KNOWN_RESOURCES_LIMIT, by_distance);
+    closest.truncate(KNOWN_RESOURCES_LIMIT);
+  }
+  closest.sort_by_key(by_distance);
+  let resources: rhai::Array = closest
+    .into_iter()
+    .map(|(x, y, kind)| {
+      let mut entry = position(x, y);
+      entry.insert("kind".into(), kind.into());
+      entry.into()
+    })
+    .collect();
+
+  let stations: rhai::Array = world
+    .stations
+    .iter()
+    .map(|s| position(s.x, s.y).into())
+    .collect();
+
+  let mut view = rhai::Map::new();
+  view.insert("width".into(), (map.width as i64).into());
+  view.insert("height".into(), (map.height as i64).into());
+  view.insert("nearby".into(), nearby.into());
+  view.insert("resources".into(), resources.into());
+  view.insert("stations".into(), stations.into());
+  view
+}
+
+impl ScriptBehaviour {
+  pub fn load(path: impl Into<PathBuf>) -> Result<Self, String> {
+    let path = path.into();
+
+    let mut engine = Engine::new();
+    engine.set_max_call_levels(32);
+    engine.set_max_array_size(10_000);
+    engine.set_max_string_size(10_000);
+    engine.on_progress(|ops| {
+      USED.set(ops);
+      (ops > ALLOWED.get()).then(|| "budget".into())
+    });
+    engine
+      .register_type_with_name::<ScriptAction>("Action")
+      .register_fn("move_by", |dx: i64, dy: i64| ScriptAction::Move(dx, dy))
+      .register_fn("go_to", |x: i64, y: i64| ScriptAction::GoTo(x, y))
+      .register_fn("collect", || ScriptAction::Collect)
+      .register_fn("unload", || ScriptAction::Unload)
+      .register_fn("wait", || ScriptAction::Wait);
+
+    let behaviour = Self {
+      name: path
+        .file_stem()
+        .map_or("script".into(), |s| s.to_string_lossy().into_owned()),
+      path,
+      engine,
+      compiled: RwLock::new(None),
+      known: RwLock::new(Vec::new()),
+      spent: Mutex::new(HashMap::new()),
+      errors: Mutex::new(Vec::new()),
+      reported: Mutex::new(Vec::new()),
+    };
+    behaviour.compile()?;
+    Ok(behaviour)
+  }
+
+  fn compile(&self) -> Result<(), String> {
+    let ast = self
+      .engine
+      .compile_file(self.path.clone())
+      .map_err(|e| format!("{}: {e}", self.path.display()))?;
+    *self.compiled.write().unwrap() = Some(Compiled {
+      ast,
+      modified: modified(&self.path),
+    });
+    Ok(())
+  }
+
+  fn report(&self, robot: Option<usize>, error: String) {
+    self.errors.lock().unwrap().push((robot, error));
+  }
+
+  fn to_action(&self, action: ScriptAction, robot: &RobotView, world: &WorldView) -> Action {
+    let target = |x: i64, y: i64| {
+      (x >= 0 && y >= 0 && (x as usize) < world.map.width && (y as usize) < world.map.height)
+        .then_some((x as usize, y as usize))
+    };
+
+    match action {
+      ScriptAction::Move(dx, dy) if dx.abs() + dy.abs() == 1 => {
+        let (x, y) = (robot.x as i64 + dx, robot.y as i64 + dy);
+        match target(x, y) {
+          Some((x, y)) => Action::Step(RobotTask::Exploring(x, y), dx as isize, dy as isize),
+          None => Action::Idle,
+        }
+      }
+      ScriptAction::Move(dx, dy) => {
+        self.report(Some(robot.index), Msg::ScriptSingleStep(dx, dy).to_string());
+        Action::Idle
+      }
+      ScriptAction::GoTo(x, y) => {
+        let Some((x, y)) = target(x, y) else {
+          return Action::Idle;
+        };
+        let task = RobotTask::Exploring(x, y);
+        match next_step_towards(robot.x, robot.y, x, y, world.map, world.resources_revealed) {
+          Some((dx, dy)) => Action::Step(task, dx, dy),
+          None => Action::Hold(task),
+        }
+      }
+      // La collecte a lieu d'elle-même sur la case où le robot s'arrête
+      ScriptAction::Collect => Action::Hold(RobotTask::Collecting(robot.x, robot.y)),
+      ScriptAction::Unload => return_to_nearest_station(robot, world),
+      ScriptAction::Wait => Action::Idle,
+    }
+  }
+}
+
+impl RobotBehaviour for ScriptBehaviour {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn begin_tick(&self, map: &Map, resources_revealed: bool) {
+    self.spent.lock().unwrap().clear();
+    *self.known.write().unwrap() = known_resources(map, resources_revealed);
+
+    let stale = self
+      .compiled
+      .read()
+      .unwrap()
+      .as_ref()
+      .is_none_or(|c| c.modified != modified(&self.path));
+    if stale && let Err(e) = self.compile() {
+      // On garde la version précédente tant que la nouvelle ne compile pas
+      self.report(None, e);
+      if let Some(compiled) = self.compiled.write().unwrap().as_mut() {
+        compiled.modified = modified(&self.path);
+      }
+    }
+  }
+
+  fn decide(&self, robot: &RobotView, world: &WorldView) -> Action {
+    let compiled = self.compiled.read().unwrap();
+    let Some(compiled) = compiled.as_ref() else {
+      return Action::Idle;
+    };
+
+    let spent = self.spent.lock().unwrap().get(&robot.index).copied();
+    ALLOWED.set(OPERATIONS_PER_TICK.saturating_sub(spent.unwrap_or(0)));
+    USED.set(0);
+    let result = self.engine.call_fn::<Dynamic>(
+      &mut Scope::new(),
+      &compiled.ast,
+      "decide",
+      (
+        robot_map(robot),
+        world_map(robot, world, &self.known.read().unwrap()),
+      ),
+    );
+    *self.spent.lock().unwrap().entry(robot.index).or_insert(0) += USED.get();
+
+    match result {
+      Ok(value) => match value.try_cast::<ScriptAction>() {
+        Some(action) => self.to_action(action, robot, world),
+        None => {
+          self.report(Some(robot.index), Msg::ScriptNoAction.to_string());
+          Action::Idle
+        }
+      },
+      Err(e) => {
+        match *e {
+          EvalAltResult::ErrorTerminated(..) => self.report(
+            Some(robot.index),
+            Msg::ScriptBudget(OPERATIONS_PER_TICK).to_string(),
+          ),
+          _ => self.report(Some(robot.index), e.to_string()),
+        }
+        Action::Idle
+      }
+    }
+  }
+
+  // Triées par robot pour ne pas dépendre de l'ordre des fils ; une erreur qui se répète
+  // d'un tour à l'autre n'est signalée que la première fois
+  fn take_errors(&self) -> Vec<String> {
+    let mut errors = std::mem::take(&mut *self.errors.lock().unwrap());
+    errors.sort_by_key(|(robot, _)| *robot);
+
+    let mut current: Vec<String> = Vec::new();
+    for (_, error) in errors {
+      if !current.contains(&error) {
+        current.push(error);
+      }
+    }
+    let previous = std::mem::replace(&mut *self.reported.lock().unwrap(), current.clone());
+    current.retain(|error| !previous.contains(error));
+    current
+  }
+}
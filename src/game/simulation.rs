@@ -0,0 +1,77 @@
+use crate::Config;
+use crate::GameState;
+use crate::error::{CommandError, GameError};
+use crate::game::command::{Command, apply_command};
+use crate::game::events::SimEvent;
+use crate::game::game_automation::automate_all_robots;
+use crate::game::invariants::{check_invariants, diagnostic_dump, resource_totals};
+use crate::game::script::ScriptBehaviour;
+use crate::robot::robot::RobotType;
+use crate::utils::theme::Theme;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// Point d'entrée de la simulation, sans terminal : le jeu, les bots et les
+// outils d'analyse la font avancer tour par tour
+pub struct Simulation {
+  pub state: GameState,
+  pub automation: bool,
+  // Vérifie les invariants après chaque tour et s'arrête avec un rapport au premier écart
+  pub check_invariants: bool,
+  // Symboles des textes produits par la simulation (inspection, rapport d'invariants)
+  pub theme: Theme,
+}
+
+impl Simulation {
+  pub fn new(config: &Config) -> Result<Self, GameError> {
+    let mut sim = Self::from_state(GameState::try_new(config)?);
+    sim.check_invariants = config.check_invariants;
+    sim.theme = config.theme;
+    Ok(sim)
+  }
+
+  pub fn from_state(state: GameState) -> Self {
+    Self {
+      state,
+      automation: true,
+      check_invariants: false,
+      theme: Theme::default(),
+    }
+  }
+
+  // Confie les robots de chaque type au script correspondant
+  pub fn load_scripts(&mut self, scripts: &[(RobotType, PathBuf)]) -> Result<(), GameError> {
+    for (robot_type, path) in scripts {
+      let behaviour = ScriptBehaviour::load(path).map_err(GameError::Script)?;
+      self
+        .state
+        .behaviours
+        .set_for_type(*robot_type, Arc::new(behaviour));
+    }
+    Ok(())
+  }
+
+  // Simule un tour
+  pub fn step(&mut self) {
+    if !self.check_invariants {
+      automate_all_robots(&mut self.state, self.automation);
+      return;
+    }
+
+    let before = resource_totals(&self.state);
+    automate_all_robots(&mut self.state, self.automation);
+    let violations = check_invariants(&self.state, before);
+    if !violations.is_empty() {
+      panic!("{}", diagnostic_dump(&self.state, &violations, &self.theme));
+    }
+  }
+
+  pub fn apply(&mut self, command: Command) -> Result<String, CommandError> {
+    apply_command(&mut self.state, command, &self.theme)
+  }
+
+  // Événements survenus depuis le dernier appel
+  pub fn events(&mut self) -> Vec<SimEvent> {
+    std::mem::take(&mut self.state.events)
+  }
+}
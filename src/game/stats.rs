@@ -0,0 +1,150 @@
+use crate::map::map::Map;
+use crate::robot::robot::{ResourceType, Robot, RobotTask};
+use crate::station::station::Station;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+// Historique conservé en mémoire, les plus anciens tours sont oubliés au-delà
+pub const MAX_SAMPLES: usize = 50_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RobotActivity {
+  Moving,
+  Idle,
+  Blocked,
+  Unloading,
+}
+
+// Mesures relevées à la fin d'un tour
+#[derive(Debug, Clone)]
+pub struct TickSample {
+  pub tick: u64,
+  pub station_inventory: [u32; 3],
+  pub zone_remaining: Vec<(u32, u32)>,
+  pub moving: u32,
+  pub idle: u32,
+  pub blocked: u32,
+  pub unloading: u32,
+  pub deliveries: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct Stats {
+  pub samples: VecDeque<TickSample>,
+  pub tick: u64,
+  pub total_deliveries: u64,
+}
+
+// Un robot immobile est bloqué s'il n'a pas encore atteint l'objectif de sa tâche
+pub fn classify_activity(
+  robot: &Robot,
+  previous: (usize, usize),
+  unloaded: bool,
+  stations: &[Station],
+) -> RobotActivity {
+  if unloaded {
+    return RobotActivity::Unloading;
+  }
+  if (robot.x, robot.y) != previous {
+    return RobotActivity::Moving;
+  }
+
+  let pos = (robot.x, robot.y);
+  let arrived = match robot.task {
+    RobotTask::Idle | RobotTask::Manual => true,
+    RobotTask::Exploring(x, y) | RobotTask::Collecting(x, y) | RobotTask::GoingTo(x, y) => {
+      pos == (x, y)
+    }
+    RobotTask::Returning(i) => stations.get(i).is_none_or(|s| (s.x, s.y) == pos),
+    RobotTask::Building(x, y) => robot.x.abs_diff(x) + robot.y.abs_diff(y) <= 1,
+  };
+
+  if arrived {
+    RobotActivity::Idle
+  } else {
+    RobotActivity::Blocked
+  }
+}
+
+impl Stats {
+  pub fn record(
+    &mut self,
+    map: &Map,
+    stations: &[Station],
+    activities: &[RobotActivity],
+    deliveries: u32,
+  ) {
+    self.tick += 1;
+    self.total_deliveries += deliveries as u64;
+
+    let mut station_inventory = [0; 3];
+    for station in stations {
+      station_inventory[0] += station.amount(&ResourceType::Mineral);
+      station_inventory[1] += station.amount(&ResourceType::Energy);
+      station_inventory[2] += station.amount(&ResourceType::Science);
+    }
+
+    let count = |activity| activities.iter().filter(|a| **a == activity).count() as u32;
+
+    self.samples.push_back(TickSample {
+      tick: self.tick,
+      station_inventory,
+      zone_remaining: map.zones.iter().map(|z| z.resource_count).collect(),
+      moving: count(RobotActivity::Moving),
+      idle: count(RobotActivity::Idle),
+      blocked: count(RobotActivity::Blocked),
+      unloading: count(RobotActivity::Unloading),
+      deliveries,
+    });
+
+    if self.samples.len() > MAX_SAMPLES {
+      self.samples.pop_front();
+    }
+  }
+
+  pub fn export_csv(&self, map: &Map, path: &str) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+
+    let mut header = vec![
+      "tick".to_string(),
+      "mineral".to_string(),
+      "energy".to_string(),
+      "science".to_string(),
+    ];
+    // Colonnes nommées d'après l'identifiant de la zone, qui ne dépend pas de la langue
+    for zone in &map.zones {
+      header.push(format!("zone{}_mineral", zone.id));
+      header.push(format!("zone{}_energy", zone.id));
+    }
+    header.extend(
+      ["moving", "idle", "blocked", "unloading", "deliveries"]
+        .iter()
+        .map(|h| h.to_string()),
+    );
+    writeln!(out, "{}", header.join(","))?;
+
+    for sample in &self.samples {
+      let mut row = vec![sample.tick.to_string()];
+      row.extend(sample.station_inventory.iter().map(|v| v.to_string()));
+      for (minerals, energies) in &sample.zone_remaining {
+        row.push(minerals.to_string());
+        row.push(energies.to_string());
+      }
+      row.extend(
+        [
+          sample.moving,
+          sample.idle,
+          sample.blocked,
+          sample.unloading,
+          sample.deliveries,
+        ]
+        .iter()
+        .map(|v| v.to_string()),
+      );
+      writeln!(out, "{}", row.join(","))?;
+    }
+
+    out.flush()
+  }
+}
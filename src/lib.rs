@@ -0,0 +1,17 @@
+pub mod config;
+pub mod error;
+pub mod game;
+pub mod map;
+pub mod robot;
+pub mod station;
+pub mod utils;
+
+pub use config::Config;
+pub use error::{CollectError, GameError, MoveError};
+pub use game::command::Command;
+pub use game::events::SimEvent;
+pub use game::game_state::GameState;
+pub use game::simulation::Simulation;
+pub use map::map::Map;
+pub use robot::robot::Robot;
+pub use station::station::Station;
@@ -1,18 +1,26 @@
-mod config;
-mod map;
-mod robot;
-mod station;
-mod utils;
-mod game;
+use ereea::Simulation;
+use ereea::game::game_loop::run_game_loop;
+use ereea::utils::locale::{Lang, set_lang};
+use ereea::{Config, GameError};
+use std::process::ExitCode;
 
-use crate::station::station::Station;
-use config::Config;
-use crate::game::game_loop::run_game_loop;
-use crate::game::game_state::GameState;
-use map::map::Map;
+fn run() -> Result<(), GameError> {
+  // Les erreurs d'options sont déjà dans la langue de l'environnement
+  set_lang(Lang::from_env());
+  let config = Config::from_args(std::env::args().skip(1))?;
+  set_lang(config.lang);
+  let mut sim = Simulation::new(&config)?;
+  sim.load_scripts(&config.scripts)?;
+  run_game_loop(sim, config.theme, config.autosave.as_deref())
+}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-  let config = Config::default();
-  let game_state = GameState::new(&config);
-  run_game_loop(game_state)
+fn main() -> ExitCode {
+  // Le terminal est déjà rendu quand l'erreur remonte jusqu'ici
+  match run() {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("{e}");
+      ExitCode::FAILURE
+    }
+  }
 }
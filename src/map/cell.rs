@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cell {
   Wall,
   Empty,
@@ -6,4 +6,15 @@ pub enum Cell {
   Energy,
   Mineral,
   Science,
+  Road,
+}
+
+impl Cell {
+  // Points de mouvement nécessaires pour entrer sur la case : une route coûte moitié moins
+  pub fn move_cost(&self) -> i32 {
+    match self {
+      Cell::Road => 1,
+      _ => 2,
+    }
+  }
 }
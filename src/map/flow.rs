@@ -0,0 +1,247 @@
+use super::cell::Cell;
+use super::grid::Grid;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+const UNREACHABLE: u32 = u32::MAX;
+
+// Destinations suivies par les champs de distances de la carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowTarget {
+  Station,
+  Mineral,
+  Energy,
+  Science,
+}
+
+impl FlowTarget {
+  pub const ALL: [FlowTarget; 4] = [
+    FlowTarget::Station,
+    FlowTarget::Mineral,
+    FlowTarget::Energy,
+    FlowTarget::Science,
+  ];
+
+  // Ressource recherchée ; les stations ne sont pas des cases de la carte
+  pub fn cell(self) -> Option<Cell> {
+    match self {
+      FlowTarget::Station => None,
+      FlowTarget::Mineral => Some(Cell::Mineral),
+      FlowTarget::Energy => Some(Cell::Energy),
+      FlowTarget::Science => Some(Cell::Science),
+    }
+  }
+}
+
+// Prochain pas vers la destination la plus proche, lu dans un champ de distances
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hop {
+  // Aucun pas quand le robot est déjà sur la destination
+  pub step: Option<(isize, isize)>,
+  pub target: (usize, usize),
+  pub distance: u32,
+}
+
+// Distance de chaque case à la plus proche des sources, et la source en question
+#[derive(Debug, Clone)]
+struct DistanceField {
+  dist: Grid<u32>,
+  source: Grid<usize>,
+}
+
+impl DistanceField {
+  fn new(width: usize, height: usize) -> Self {
+    Self {
+      dist: Grid::new(width, height, UNREACHABLE),
+      source: Grid::new(width, height, 0),
+    }
+  }
+
+  // Parcours en largeur complet depuis toutes les sources
+  fn rebuild(&mut self, passable: &Grid<bool>, sources: &[usize]) {
+    self.dist.cells_mut().fill(UNREACHABLE);
+    let mut queue = VecDeque::new();
+    for &s in sources {
+      self.dist.cells_mut()[s] = 0;
+      self.source.cells_mut()[s] = s;
+      queue.push_back(s);
+    }
+
+    while let Some(i) = queue.pop_front() {
+      let d = self.dist.cells()[i];
+      for next in passable.neighbour_indices(i) {
+        if passable.cells()[next] && self.dist.cells()[next] == UNREACHABLE {
+          self.dist.cells_mut()[next] = d + 1;
+          self.source.cells_mut()[next] = self.source.cells()[i];
+          queue.push_back(next);
+        }
+      }
+    }
+  }
+
+  // Cases dont la distance ne peut que baisser : nouvelles sources, ou cases devenues
+  // franchissables. Chacune repart de ses voisines, puis la baisse se propage
+  fn lower(&mut self, passable: &Grid<bool>, seeds: &[(usize, bool)]) {
+    let mut heap = BinaryHeap::new();
+    for &(i, is_source) in seeds {
+      let candidate = if is_source {
+        Some((0, i))
+      } else if passable.cells()[i] {
+        passable
+          .neighbour_indices(i)
+          .filter(|&n| self.dist.cells()[n] != UNREACHABLE)
+          .map(|n| (self.dist.cells()[n] + 1, self.source.cells()[n]))
+          .min_by_key(|&(d, _)| d)
+      } else {
+        None
+      };
+
+      if let Some((d, source)) = candidate
+        && d < self.dist.cells()[i]
+      {
+        self.dist.cells_mut()[i] = d;
+        self.source.cells_mut()[i] = source;
+        heap.push(Reverse((d, i)));
+      }
+    }
+
+    while let Some(Reverse((d, i))) = heap.pop() {
+      if d != self.dist.cells()[i] {
+        continue;
+      }
+      for next in passable.neighbour_indices(i) {
+        if passable.cells()[next] && d + 1 < self.dist.cells()[next] {
+          self.dist.cells_mut()[next] = d + 1;
+          self.source.cells_mut()[next] = self.source.cells()[i];
+          heap.push(Reverse((d + 1, next)));
+        }
+      }
+    }
+  }
+
+  // Seules les cases rattachées à la source disparue peuvent s'éloigner : elles forment
+  // une région d'un seul tenant, effacée puis reconstruite depuis ses bords
+  fn remove_source(&mut self, passable: &Grid<bool>, removed: usize) {
+    if self.dist.cells()[removed] != 0 || self.source.cells()[removed] != removed {
+      return;
+    }
+
+    let mut region = vec![removed];
+    let mut queue = VecDeque::from([removed]);
+    self.dist.cells_mut()[removed] = UNREACHABLE;
+    while let Some(i) = queue.pop_front() {
+      for next in passable.neighbour_indices(i) {
+        if self.dist.cells()[next] != UNREACHABLE && self.source.cells()[next] == removed {
+          self.dist.cells_mut()[next] = UNREACHABLE;
+          region.push(next);
+          queue.push_back(next);
+        }
+      }
+    }
+
+    let seeds: Vec<(usize, bool)> = region.into_iter().map(|i| (i, false)).collect();
+    self.lower(passable, &seeds);
+  }
+
+  fn hop(&self, x: usize, y: usize) -> Option<Hop> {
+    let i = self.dist.index_of(x, y);
+    let here = self.dist.cells()[i];
+    if here == 0 {
+      return Some(Hop {
+        step: None,
+        target: (x, y),
+        distance: 0,
+      });
+    }
+
+    // Première voisine dans l'ordre des directions parmi les plus proches
+    let next = self
+      .dist
+      .neighbour_indices(i)
+      .filter(|&n| self.dist.cells()[n] < here)
+      .min_by_key(|&n| self.dist.cells()[n])?;
+    let (nx, ny) = self.dist.position(next);
+    Some(Hop {
+      step: Some((nx as isize - x as isize, ny as isize - y as isize)),
+      target: self.dist.position(self.source.cells()[next]),
+      distance: self.dist.cells()[next] + 1,
+    })
+  }
+}
+
+// Champs de distances tenus à jour par la carte : un depuis les stations et un par
+// type de ressource. Ils suivent la révélation des ressources du dernier `Map::sync_flow_fields`
+#[derive(Debug, Clone, Default)]
+pub struct FlowFields {
+  revealed: Option<bool>,
+  stations: Vec<usize>,
+  passable: Grid<bool>,
+  fields: Vec<DistanceField>,
+}
+
+impl FlowFields {
+  pub fn revealed(&self) -> Option<bool> {
+    self.revealed
+  }
+
+  pub fn stations(&self) -> &[usize] {
+    &self.stations
+  }
+
+  pub fn passable(&self, index: usize) -> bool {
+    self.passable.cells()[index]
+  }
+
+  pub fn hop(&self, target: FlowTarget, x: usize, y: usize) -> Option<Hop> {
+    self.fields.get(target as usize)?.hop(x, y)
+  }
+
+  // Distance de la case à la destination la plus proche
+  pub fn distance(&self, target: FlowTarget, x: usize, y: usize) -> Option<u32> {
+    let d = self.fields.get(target as usize)?.dist[(x, y)];
+    (d != UNREACHABLE).then_some(d)
+  }
+
+  pub fn rebuild(
+    &mut self,
+    passable: Grid<bool>,
+    stations: Vec<usize>,
+    sources: impl Fn(FlowTarget) -> Vec<usize>,
+    revealed: bool,
+  ) {
+    let (width, height) = (passable.width, passable.height);
+    self.fields = FlowTarget::ALL
+      .iter()
+      .map(|&target| {
+        let mut field = DistanceField::new(width, height);
+        let sources = match target {
+          FlowTarget::Station => stations.clone(),
+          _ => sources(target),
+        };
+        field.rebuild(&passable, &sources);
+        field
+      })
+      .collect();
+    self.passable = passable;
+    self.stations = stations;
+    self.revealed = Some(revealed);
+  }
+
+  pub fn set_passable(&mut self, index: usize, passable: bool) {
+    self.passable.cells_mut()[index] = passable;
+  }
+
+  pub fn lower(&mut self, target: FlowTarget, seeds: &[(usize, bool)]) {
+    self.fields[target as usize].lower(&self.passable, seeds);
+  }
+
+  pub fn remove_source(&mut self, target: FlowTarget, index: usize) {
+    self.fields[target as usize].remove_source(&self.passable, index);
+  }
+
+  pub fn add_stations(&mut self, stations: &[usize]) {
+    let seeds: Vec<(usize, bool)> = stations.iter().map(|&s| (s, true)).collect();
+    self.stations.extend_from_slice(stations);
+    self.lower(FlowTarget::Station, &seeds);
+  }
+}
@@ -0,0 +1,169 @@
+use std::ops::{Index, IndexMut};
+
+// Déplacements vers les quatre voisines, dans l'ordre d'exploration des recherches
+pub const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+// Grille stockée d'un seul tenant, ligne par ligne : la case (x, y) est en y * width + x
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grid<T> {
+  pub width: usize,
+  pub height: usize,
+  cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+  pub fn new(width: usize, height: usize, value: T) -> Self {
+    Self {
+      width,
+      height,
+      cells: vec![value; width * height],
+    }
+  }
+}
+
+impl<T> Grid<T> {
+  pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+    let cells = (0..width * height)
+      .map(|i| f(i % width, i / width))
+      .collect();
+    Self {
+      width,
+      height,
+      cells,
+    }
+  }
+
+  pub fn index_of(&self, x: usize, y: usize) -> usize {
+    assert!(
+      x < self.width && y < self.height,
+      "({x}, {y}) hors de la grille {}x{}",
+      self.width,
+      self.height
+    );
+    y * self.width + x
+  }
+
+  pub fn position(&self, index: usize) -> (usize, usize) {
+    (index % self.width, index / self.width)
+  }
+
+  pub fn contains(&self, x: isize, y: isize) -> bool {
+    x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+  }
+
+  pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+    (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+  }
+
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+    &mut self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn cells(&self) -> &[T] {
+    &self.cells
+  }
+
+  pub fn cells_mut(&mut self) -> &mut [T] {
+    &mut self.cells
+  }
+
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  // Toutes les cases avec leur position, ligne par ligne
+  pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+    let width = self.width;
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .map(move |(i, v)| ((i % width, i / width), v))
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  // Voisines directes de (x, y) restant dans la grille
+  pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + use<T> {
+    let (width, height) = (self.width, self.height);
+    DIRECTIONS.into_iter().filter_map(move |(dx, dy)| {
+      let nx = x.checked_add_signed(dx)?;
+      let ny = y.checked_add_signed(dy)?;
+      (nx < width && ny < height).then_some((nx, ny))
+    })
+  }
+
+  // Mêmes voisines, sous forme d'indices dans le tampon
+  pub fn neighbour_indices(&self, index: usize) -> impl Iterator<Item = usize> + use<T> {
+    let width = self.width;
+    let (x, y) = self.position(index);
+    self.neighbours(x, y).map(move |(nx, ny)| ny * width + nx)
+  }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, (x, y): (usize, usize)) -> &T {
+    &self.cells[self.index_of(x, y)]
+  }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+  fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+    let i = self.index_of(x, y);
+    &mut self.cells[i]
+  }
+}
+
+// Cases déjà vues par une recherche. Repartir de zéro ne fait que changer de génération :
+// le tableau n'est ni réalloué ni remis à zéro d'une recherche à l'autre
+#[derive(Debug, Default)]
+pub struct Visited {
+  stamps: Vec<u32>,
+  generation: u32,
+}
+
+impl Visited {
+  pub fn reset(&mut self, len: usize) {
+    if self.stamps.len() != len {
+      self.stamps = vec![0; len];
+      self.generation = 0;
+    }
+    self.generation = self.generation.wrapping_add(1);
+    // Après un tour complet du compteur, d'anciennes marques redeviendraient valides
+    if self.generation == 0 {
+      self.stamps.fill(0);
+      self.generation = 1;
+    }
+  }
+
+  // Marque la case, et indique si elle ne l'était pas encore
+  pub fn insert(&mut self, index: usize) -> bool {
+    let fresh = self.stamps[index] != self.generation;
+    self.stamps[index] = self.generation;
+    fresh
+  }
+
+  pub fn contains(&self, index: usize) -> bool {
+    self.stamps[index] == self.generation
+  }
+}
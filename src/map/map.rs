@@ -1,33 +1,88 @@
 use super::cell::Cell;
+use super::flow::{FlowFields, FlowTarget, Hop};
+use super::grid::Grid;
 use super::zone::Zone;
+use crate::error::GameError;
 use crate::robot::robot::Robot;
-use crate::robot::robot::RobotType;
 use crate::station::station::Station;
+use crate::utils::camera::Camera;
+use crate::utils::locale::Msg;
 use crate::utils::noise::generate_noise;
+use crate::utils::theme::Theme;
 use colored::*;
 
+// Largeur de la mini-carte, en caractères
+const MINIMAP_WIDTH: usize = 25;
+
+// Couleur de fond optionnelle de chaque case, utilisée par les calques
+pub type Background = Grid<Option<(u8, u8, u8)>>;
+
+fn with_background(
+  symbol: ColoredString,
+  background: Option<&Background>,
+  x: usize,
+  y: usize,
+) -> ColoredString {
+  match background.and_then(|b| b[(x, y)]) {
+    Some((r, g, b)) => symbol.on_truecolor(r, g, b),
+    None => symbol,
+  }
+}
+
+// Paramètres d'affichage de la carte : portion visible, apparence et calque
+pub struct MapView<'a> {
+  pub camera: &'a Camera,
+  pub theme: &'a Theme,
+  pub background: Option<&'a Background>,
+}
+
 pub struct Map {
   pub width: usize,
   pub height: usize,
-  pub grid: Vec<Vec<Cell>>,
+  // Privée : toute modification passe par `set_cell`, qui tient à jour les compteurs des zones
+  // et les champs de distances
+  grid: Grid<Cell>,
   pub zones: Vec<Zone>,
   pub current_turn: u32,
+  // Nombre de passages de robots sur chaque case
+  pub traffic: Grid<u32>,
+  flow: FlowFields,
 }
 
 impl Map {
-  pub fn new(width: usize, height: usize, seed: u32) -> Self {
+  // Bordure d'obstacles comprise, il faut au moins une case libre au centre
+  pub const MIN_SIZE: (usize, usize) = (3, 3);
+
+  fn check_size(width: usize, height: usize) -> Result<(), GameError> {
+    if width < Self::MIN_SIZE.0 || height < Self::MIN_SIZE.1 {
+      return Err(GameError::MapTooSmall {
+        width,
+        height,
+        min: Self::MIN_SIZE,
+      });
+    }
+    Ok(())
+  }
+
+  pub fn try_new(width: usize, height: usize, seed: u32) -> Result<Self, GameError> {
+    Self::check_size(width, height)?;
     let mut grid = generate_noise(width, height, seed);
 
-    for x in 0..width {
-      grid[0][x] = Cell::Obstacle;
-      grid[height - 1][x] = Cell::Obstacle;
-    }
+    grid.row_mut(0).fill(Cell::Obstacle);
+    grid.row_mut(height - 1).fill(Cell::Obstacle);
 
     for y in 0..height {
-      grid[y][0] = Cell::Obstacle;
-      grid[y][width - 1] = Cell::Obstacle;
+      grid[(0, y)] = Cell::Obstacle;
+      grid[(width - 1, y)] = Cell::Obstacle;
     }
 
+    Self::from_grid(grid)
+  }
+
+  // Carte aux cases données, bordure comprise
+  pub fn from_grid(grid: Grid<Cell>) -> Result<Self, GameError> {
+    let (width, height) = (grid.width, grid.height);
+    Self::check_size(width, height)?;
     let zones = Self::create_zones(width, height);
 
     let mut map = Self {
@@ -36,10 +91,15 @@ impl Map {
       grid,
       zones,
       current_turn: 0,
+      traffic: Grid::new(width, height, 0),
+      flow: FlowFields::default(),
     };
 
-    map.count_resources_in_zones();
-    map
+    let counts = map.count_resources_in_zones();
+    for (zone, count) in map.zones.iter_mut().zip(counts) {
+      zone.resource_count = count;
+    }
+    Ok(map)
   }
 
   fn create_zones(width: usize, height: usize) -> Vec<Zone> {
@@ -48,9 +108,7 @@ impl Map {
     let zone_width = width / 2;
     let zone_height = height / 2;
 
-    let zone_names = vec!["Nord-Ouest", "Nord-Est", "Sud-Ouest", "Sud-Est"];
-
-    for (i, name) in zone_names.iter().enumerate() {
+    for i in 0..4 {
       let row = i / 2;
       let col = i % 2;
 
@@ -67,30 +125,189 @@ impl Map {
         (row + 1) * zone_height - 1
       };
 
-      zones.push(Zone::new(i, name.to_string(), min_x, max_x, min_y, max_y));
+      zones.push(Zone::new(
+        i,
+        Msg::ZoneName(i).to_string(),
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+      ));
     }
 
     zones
   }
 
-  fn count_resources_in_zones(&mut self) {
-    for zone in &mut self.zones {
-      let mut minerals = 0;
-      let mut energies = 0;
-
-      for y in zone.min_y..=zone.max_y {
-        for x in zone.min_x..=zone.max_x {
-          if y < self.height && x < self.width {
-            match self.grid[y][x] {
-              Cell::Mineral => minerals += 1,
-              Cell::Energy => energies += 1,
+  // Recompte complet des minerais et énergies de chaque zone
+  pub fn count_resources_in_zones(&self) -> Vec<(u32, u32)> {
+    self
+      .zones
+      .iter()
+      .map(|zone| {
+        let mut count = (0, 0);
+        for y in zone.min_y..=zone.max_y.min(self.height - 1) {
+          for x in zone.min_x..=zone.max_x.min(self.width - 1) {
+            match self.grid[(x, y)] {
+              Cell::Mineral => count.0 += 1,
+              Cell::Energy => count.1 += 1,
               _ => {}
             }
           }
         }
+        count
+      })
+      .collect()
+  }
+
+  pub fn grid(&self) -> &Grid<Cell> {
+    &self.grid
+  }
+
+  // Remplace une case et renvoie l'ancienne, en ajustant le compteur de sa zone
+  pub fn set_cell(&mut self, x: usize, y: usize, cell: Cell) -> Cell {
+    let previous = std::mem::replace(&mut self.grid[(x, y)], cell);
+    if previous != cell
+      && let Some(zone) = self.zones.iter_mut().find(|zone| zone.contains_point(x, y))
+    {
+      for (kind, delta) in [(previous, -1i32), (cell, 1)] {
+        let count = match kind {
+          Cell::Mineral => &mut zone.resource_count.0,
+          Cell::Energy => &mut zone.resource_count.1,
+          _ => continue,
+        };
+        *count = count
+          .checked_add_signed(delta)
+          .expect("compteur de ressources de la zone désynchronisé");
       }
+    }
 
-      zone.resource_count = (minerals, energies);
+    #[cfg(debug_assertions)]
+    {
+      let counts: Vec<(u32, u32)> = self.zones.iter().map(|z| z.resource_count).collect();
+      debug_assert_eq!(counts, self.count_resources_in_zones());
+    }
+
+    if previous != cell {
+      self.update_flow(self.grid.index_of(x, y), previous);
+    }
+    previous
+  }
+
+  // Une case de ressource cachée bloque le passage tant que sa zone n'est pas débloquée
+  pub fn blocks(&self, index: usize, resources_revealed: bool) -> bool {
+    let cell = self.grid.cells()[index];
+    if matches!(cell, Cell::Wall | Cell::Obstacle) {
+      return true;
+    }
+    if resources_revealed || !matches!(cell, Cell::Mineral | Cell::Energy) {
+      return false;
+    }
+    let (x, y) = self.grid.position(index);
+    !self.is_resource_accessible(x, y)
+  }
+
+  // Une ressource n'est une destination que si on peut marcher dessus
+  fn is_flow_source(&self, target: FlowTarget, index: usize, revealed: bool) -> bool {
+    target.cell() == Some(self.grid.cells()[index]) && !self.blocks(index, revealed)
+  }
+
+  // Reconstruit les champs de distances si la révélation ou les stations ont changé
+  pub fn sync_flow_fields(&mut self, stations: &[(usize, usize)], resources_revealed: bool) {
+    let stations: Vec<usize> = stations
+      .iter()
+      .map(|&(x, y)| self.grid.index_of(x, y))
+      .collect();
+    if self.flow.revealed() == Some(resources_revealed) {
+      // Les avant-postes s'ajoutent en fin de liste : seules leurs distances sont à propager
+      if let Some(added) = stations.strip_prefix(self.flow.stations()) {
+        if !added.is_empty() {
+          self.flow.add_stations(added);
+        }
+        return;
+      }
+    }
+
+    let passable = Grid::from_fn(self.width, self.height, |x, y| {
+      !self.blocks(self.grid.index_of(x, y), resources_revealed)
+    });
+    let sources = |target| {
+      (0..self.grid.len())
+        .filter(|&i| self.is_flow_source(target, i, resources_revealed))
+        .collect()
+    };
+    let mut flow = FlowFields::default();
+    flow.rebuild(passable, stations, sources, resources_revealed);
+    self.flow = flow;
+  }
+
+  // Prochain pas vers la destination la plus proche, d'après les champs à jour
+  pub fn flow_hop(&self, target: FlowTarget, x: usize, y: usize) -> Option<Hop> {
+    self.flow.hop(target, x, y)
+  }
+
+  pub fn flow_distance(&self, target: FlowTarget, x: usize, y: usize) -> Option<u32> {
+    self.flow.distance(target, x, y)
+  }
+
+  fn update_flow(&mut self, index: usize, previous: Cell) {
+    let Some(revealed) = self.flow.revealed() else {
+      return;
+    };
+    let was_passable = self.flow.passable(index);
+    let passable = !self.blocks(index, revealed);
+    // Une case qui se ferme peut rallonger n'importe quel chemin : on repart de zéro
+    if was_passable && !passable {
+      let stations: Vec<(usize, usize)> = self
+        .flow
+        .stations()
+        .iter()
+        .map(|&s| self.grid.position(s))
+        .collect();
+      self.flow = FlowFields::default();
+      self.sync_flow_fields(&stations, revealed);
+      return;
+    }
+
+    self.flow.set_passable(index, passable);
+    for target in FlowTarget::ALL {
+      let was_source = target.cell() == Some(previous) && was_passable;
+      let is_source = self.is_flow_source(target, index, revealed);
+      if was_source && !is_source {
+        self.flow.remove_source(target, index);
+      }
+      if (passable && !was_passable) || (is_source && !was_source) {
+        self.flow.lower(target, &[(index, is_source)]);
+      }
+    }
+  }
+
+  // Débloquer une zone rend ses ressources franchissables et atteignables
+  pub fn unlock_zone(&mut self, id: usize) {
+    let Some(zone) = self.zones.get_mut(id) else {
+      return;
+    };
+    zone.unlock();
+    let (min_x, max_x, min_y, max_y) = (zone.min_x, zone.max_x, zone.min_y, zone.max_y);
+
+    if self.flow.revealed() != Some(false) {
+      return;
+    }
+    let mut opened = Vec::new();
+    for y in min_y..=max_y.min(self.height - 1) {
+      for x in min_x..=max_x.min(self.width - 1) {
+        let i = self.grid.index_of(x, y);
+        if !self.flow.passable(i) && !self.blocks(i, false) {
+          self.flow.set_passable(i, true);
+          opened.push(i);
+        }
+      }
+    }
+    for target in FlowTarget::ALL {
+      let seeds: Vec<(usize, bool)> = opened
+        .iter()
+        .map(|&i| (i, self.is_flow_source(target, i, false)))
+        .collect();
+      self.flow.lower(target, &seeds);
     }
   }
 
@@ -98,17 +315,20 @@ impl Map {
     self.zones.iter().find(|zone| zone.contains_point(x, y))
   }
 
-  pub fn unlock_zone_with_science(&mut self, science_pos: (usize, usize)) -> Option<String> {
+  // Renvoie la zone débloquée, si elle ne l'était pas déjà
+  pub fn unlock_zone_with_science(&mut self, science_pos: (usize, usize)) -> Option<usize> {
     let (x, y) = science_pos;
 
-    for zone in &mut self.zones {
-      if zone.contains_point(x, y) && !zone.is_unlocked {
-        zone.unlock();
-        return Some(format!("Zone {} débloquée", zone.name));
-      }
-    }
+    let zone = self
+      .zones
+      .iter()
+      .position(|zone| zone.contains_point(x, y) && !zone.is_unlocked)?;
+    self.unlock_zone(zone);
+    Some(zone)
+  }
 
-    None
+  pub fn is_border(&self, x: usize, y: usize) -> bool {
+    x == 0 || y == 0 || x == self.width - 1 || y == self.height - 1
   }
 
   pub fn is_resource_accessible(&self, x: usize, y: usize) -> bool {
@@ -141,72 +361,251 @@ impl Map {
     (minerals, energies)
   }
 
+  pub fn record_visit(&mut self, x: usize, y: usize) {
+    self.traffic[(x, y)] += 1;
+  }
+
   pub fn next_turn(&mut self) {
     self.current_turn += 1;
   }
 
-  pub fn print_map(&self, robots: &[Robot], station: &Station, resources_revealed: bool) {
-    for (y, row) in self.grid.iter().enumerate() {
-      for (x, cell) in row.iter().enumerate() {
-        // Vérifie si un robot est sur cette case
-        if let Some((_index, robot)) = robots
+  // Case la plus représentative d'un bloc de zoom x zoom : les obstacles s'ils
+  // sont majoritaires, sinon le contenu le plus intéressant
+  fn representative(&self, x0: usize, y0: usize, zoom: usize) -> (usize, usize) {
+    let cells: Vec<(usize, usize)> = (y0..(y0 + zoom).min(self.height))
+      .flat_map(|y| (x0..(x0 + zoom).min(self.width)).map(move |x| (x, y)))
+      .collect();
+
+    let blocked = cells
+      .iter()
+      .filter(|&&(x, y)| matches!(self.grid[(x, y)], Cell::Wall | Cell::Obstacle))
+      .count();
+    if blocked * 2 >= cells.len() {
+      return (x0, y0);
+    }
+
+    let priority = |cell: Cell| match cell {
+      Cell::Science => 0,
+      Cell::Mineral => 1,
+      Cell::Energy => 2,
+      Cell::Road => 3,
+      Cell::Empty => 4,
+      Cell::Wall | Cell::Obstacle => 5,
+    };
+    cells
+      .into_iter()
+      .min_by_key(|&(x, y)| priority(self.grid[(x, y)]))
+      .unwrap_or((x0, y0))
+  }
+
+  pub fn print_map(
+    &self,
+    robots: &[Robot],
+    stations: &[Station],
+    resources_revealed: bool,
+    selected: Option<usize>,
+    view: &MapView,
+  ) {
+    let MapView {
+      camera,
+      theme,
+      background,
+    } = *view;
+    let palette = theme.palette();
+    let zoom = camera.zoom;
+    let (span_w, span_h) = camera.span(self);
+    let in_block = |bx: usize, by: usize, x: usize, y: usize| {
+      x >= bx && x < bx + zoom && y >= by && y < by + zoom
+    };
+
+    for by in (camera.y..camera.y + span_h).step_by(zoom) {
+      for bx in (camera.x..camera.x + span_w).step_by(zoom) {
+        let (x, y) = self.representative(bx, by, zoom);
+        let cell = &self.grid[(x, y)];
+
+        // Vérifie si un robot est sur cette case (ou dans ce bloc)
+        if let Some((index, robot)) = robots
           .iter()
           .enumerate()
-          .find(|(_, r)| r.x == x && r.y == y)
+          .find(|(_, r)| in_block(bx, by, r.x, r.y))
         {
-          let symbol = match robot.robot_type {
-            RobotType::Explorator => "👽",
-            _ => "🤖",
-          };
-          print!("{}", symbol);
-        } else if x == station.x && y == station.y {
-          print!("{}", "🏭");
+          let symbol = theme.robot(robot.robot_type);
+          // Le robot sélectionné est surligné, un robot en détresse en rouge
+          if selected == Some(index) {
+            print!("{}", symbol.on_color(palette.selected));
+          } else if robot.telemetry.needs_help() {
+            print!("{}", symbol.on_color(palette.help));
+          } else {
+            print!("{}", with_background(symbol, background, x, y));
+          }
+        } else if stations.iter().any(|s| in_block(bx, by, s.x, s.y)) {
+          print!("{}", with_background(theme.station(), background, x, y));
         } else {
-          let is_accessible = self.is_resource_accessible(x, y);
-          let symbol = match cell {
-            Cell::Wall | Cell::Obstacle => "██".bright_black(),
-            Cell::Empty => "  ".white(),
-            Cell::Science => "🧪".purple().bold(),
-            Cell::Mineral => {
-              if resources_revealed || is_accessible {
-                "💎".blue().bold()
-              } else {
-                "❓".red().bold()
-              }
-            }
-            Cell::Energy => {
-              if resources_revealed || is_accessible {
-                "⚡".yellow().bold()
-              } else {
-                "❓".red().bold()
-              }
-            }
-          };
-          print!("{}", symbol);
+          let visible = resources_revealed || self.is_resource_accessible(x, y);
+          let symbol = theme.cell(*cell, visible);
+          print!("{}", with_background(symbol, background, x, y));
         }
       }
       println!();
     }
   }
 
-  pub fn update_zone_resource_counts(&mut self) {
-    for zone in &mut self.zones {
-      let mut minerals = 0;
-      let mut energies = 0;
-
-      for y in zone.min_y..=zone.max_y {
-        for x in zone.min_x..=zone.max_x {
-          if y < self.height && x < self.width {
-            match self.grid[y][x] {
-              Cell::Mineral => minerals += 1,
-              Cell::Energy => energies += 1,
-              _ => {}
-            }
+  // Vue d'ensemble, un caractère par région, la zone affichée en surbrillance
+  pub fn print_minimap(&self, robots: &[Robot], stations: &[Station], view: &MapView) {
+    let MapView { camera, theme, .. } = *view;
+    let palette = theme.palette();
+    let region_w = self.width.div_ceil(MINIMAP_WIDTH).max(1);
+    let region_h = (region_w / 2).max(1);
+
+    for ry in (0..self.height).step_by(region_h) {
+      for rx in (0..self.width).step_by(region_w) {
+        let in_region =
+          |x: usize, y: usize| x >= rx && x < rx + region_w && y >= ry && y < ry + region_h;
+        let cells = (ry..(ry + region_h).min(self.height))
+          .flat_map(|y| (rx..(rx + region_w).min(self.width)).map(move |x| (x, y)));
+
+        let (mut blocked, mut resources, mut total) = (0, 0, 0);
+        for (x, y) in cells {
+          total += 1;
+          match self.grid[(x, y)] {
+            Cell::Wall | Cell::Obstacle => blocked += 1,
+            Cell::Mineral | Cell::Energy | Cell::Science => resources += 1,
+            _ => {}
           }
         }
+
+        let symbol = if robots.iter().any(|r| in_region(r.x, r.y)) {
+          "@".color(palette.robot).bold()
+        } else if stations.iter().any(|s| in_region(s.x, s.y)) {
+          "S".color(palette.station).bold()
+        } else if blocked * 2 >= total {
+          "#".color(palette.obstacle)
+        } else if resources * 4 >= total {
+          "*".color(palette.energy)
+        } else {
+          ".".white()
+        };
+
+        if camera.contains(rx, ry, self) {
+          print!("{}", symbol.on_color(palette.viewport));
+        } else {
+          print!("{}", symbol);
+        }
       }
+      println!();
+    }
+  }
+}
 
-      zone.resource_count = (minerals, energies);
+#[cfg(test)]
+impl Map {
+  // Carte de test décrite ligne par ligne avec les caractères de la sauvegarde
+  pub fn from_rows(rows: &[&str]) -> Self {
+    use crate::game::save::cell_to_char;
+    let cells = [
+      Cell::Wall,
+      Cell::Obstacle,
+      Cell::Empty,
+      Cell::Energy,
+      Cell::Mineral,
+      Cell::Science,
+      Cell::Road,
+    ];
+    let grid = Grid::from_fn(rows[0].len(), rows.len(), |x, y| {
+      let c = rows[y].as_bytes()[x] as char;
+      *cells
+        .iter()
+        .find(|&&cell| cell_to_char(cell) == c)
+        .expect("case inconnue")
+    });
+    Self::from_grid(grid).expect("carte trop petite")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn zones_cover_odd_sizes_exactly_once() {
+    for (width, height) in [(3, 3), (7, 5), (13, 9), (51, 15)] {
+      let zones = Map::create_zones(width, height);
+      assert_eq!(zones.len(), 4);
+      for y in 0..height {
+        for x in 0..width {
+          let owners = zones.iter().filter(|z| z.contains_point(x, y)).count();
+          assert_eq!(owners, 1, "({x}, {y}) sur {width}x{height}");
+        }
+      }
+      assert_eq!(zones[3].max_x, width - 1);
+      assert_eq!(zones[3].max_y, height - 1);
     }
   }
+
+  #[test]
+  fn too_small_maps_are_rejected() {
+    assert!(matches!(
+      Map::try_new(2, 10, 0),
+      Err(GameError::MapTooSmall { .. })
+    ));
+    assert!(matches!(
+      Map::try_new(10, 0, 0),
+      Err(GameError::MapTooSmall { .. })
+    ));
+    assert!(Map::try_new(3, 3, 0).is_ok());
+  }
+
+  #[test]
+  fn set_cell_keeps_zone_counts() {
+    let mut map = Map::from_rows(&["OOOOOO", "OM.EEO", "O.M..O", "OOOOOO"]);
+    assert_eq!(map.zones[0].resource_count, (1, 0));
+    assert_eq!(map.zones[1].resource_count, (0, 2));
+    assert_eq!(map.zones[2].resource_count, (1, 0));
+
+    assert_eq!(map.set_cell(4, 1, Cell::Empty), Cell::Energy);
+    assert_eq!(map.set_cell(2, 2, Cell::Energy), Cell::Mineral);
+    assert_eq!(map.zones[1].resource_count, (0, 1));
+    assert_eq!(map.zones[2].resource_count, (0, 1));
+  }
+
+  #[test]
+  fn flow_fields_follow_cell_changes() {
+    let mut map = Map::from_rows(&[
+      "OOOOOOO", //
+      "O..O.MO", //
+      "O..O..O", //
+      "O.....O", //
+      "OOOOOOO",
+    ]);
+    map.sync_flow_fields(&[(1, 1)], true);
+    assert_eq!(map.flow_distance(FlowTarget::Station, 4, 1), Some(7));
+    let hop = map.flow_hop(FlowTarget::Mineral, 1, 1).unwrap();
+    assert_eq!(hop.target, (5, 1));
+    assert_eq!(hop.distance, 8);
+
+    // Une route à travers l'obstacle raccourcit le trajet
+    map.set_cell(3, 1, Cell::Road);
+    assert_eq!(map.flow_distance(FlowTarget::Station, 4, 1), Some(3));
+    assert_eq!(
+      map.flow_hop(FlowTarget::Mineral, 1, 1).unwrap().step,
+      Some((1, 0))
+    );
+
+    // Le minerai ramassé n'est plus une destination
+    map.set_cell(5, 1, Cell::Empty);
+    assert_eq!(map.flow_hop(FlowTarget::Mineral, 1, 1), None);
+    assert_eq!(map.flow_distance(FlowTarget::Mineral, 5, 1), None);
+  }
+
+  #[test]
+  fn unlocking_a_zone_opens_its_resources() {
+    let mut map = Map::from_rows(&["OOOOOO", "O..MEO", "O....O", "OOOOOO"]);
+    map.sync_flow_fields(&[(1, 1)], false);
+    assert_eq!(map.flow_hop(FlowTarget::Mineral, 1, 1), None);
+
+    map.unlock_zone(1);
+    let hop = map.flow_hop(FlowTarget::Mineral, 1, 1).unwrap();
+    assert_eq!((hop.target, hop.distance), ((3, 1), 2));
+    assert_eq!(map.flow_distance(FlowTarget::Station, 4, 1), Some(3));
+  }
 }
@@ -1,3 +1,6 @@
 pub mod cell;
+pub mod flow;
+pub mod grid;
+#[allow(clippy::module_inception)]
 pub mod map;
 pub mod zone;
@@ -39,3 +39,20 @@ impl Zone {
     self.is_unlocked = true;
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn contains_point_includes_bounds() {
+    let zone = Zone::new(0, "A".into(), 2, 5, 1, 3);
+    assert!(zone.contains_point(2, 1));
+    assert!(zone.contains_point(5, 3));
+    assert!(zone.contains_point(4, 2));
+    assert!(!zone.contains_point(1, 2));
+    assert!(!zone.contains_point(6, 2));
+    assert!(!zone.contains_point(3, 0));
+    assert!(!zone.contains_point(3, 4));
+  }
+}
@@ -1,12 +1,21 @@
+use crate::error::{CollectError, MoveError};
 use crate::map::cell::Cell;
 use crate::map::map::Map;
+use crate::robot::telemetry::RobotTelemetry;
 use crate::station::station::Station;
-use std::collections::{HashMap};
+use crate::utils::locale::Msg;
+use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// Points de mouvement gagnés par tour : de quoi faire un pas hors route
+pub const BASE_SPEED: u32 = 2;
+pub const MAX_SPEED: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum RobotType {
   Explorator,
   Collector,
+  Builder,
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -16,87 +25,190 @@ pub enum ResourceType {
   Science,
 }
 
-#[derive(Debug)]
+impl ResourceType {
+  // Place occupée par une unité dans la soute : un échantillon de science pèse lourd
+  pub fn weight(&self) -> u32 {
+    match self {
+      ResourceType::Mineral | ResourceType::Energy => 1,
+      ResourceType::Science => 3,
+    }
+  }
+}
+
+// Soute d'un type de robot : charge maximale et ressources qu'elle peut recevoir
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CargoHold {
+  pub capacity: u32,
+  pub accepts: &'static [ResourceType],
+}
+
+impl RobotType {
+  pub fn cargo_hold(self) -> CargoHold {
+    match self {
+      // De quoi rapporter deux échantillons
+      RobotType::Explorator => CargoHold {
+        capacity: 6,
+        accepts: &[ResourceType::Science],
+      },
+      RobotType::Collector => CargoHold {
+        capacity: 5,
+        accepts: &[ResourceType::Mineral, ResourceType::Energy],
+      },
+      RobotType::Builder => CargoHold {
+        capacity: 0,
+        accepts: &[],
+      },
+    }
+  }
+}
+
+// Ce que le robot est en train de faire, affiché dans le panneau du robot sélectionné
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RobotTask {
+  Idle,
+  Manual,
+  Exploring(usize, usize),
+  Collecting(usize, usize),
+  Returning(usize),
+  Building(usize, usize),
+  GoingTo(usize, usize),
+}
+
+impl fmt::Display for RobotTask {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", Msg::Task(*self))
+  }
+}
+
+// Bilan d'un déchargement à la station
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Unloaded {
+  pub science: bool,
+  // Zones débloquées par la science déposée
+  pub zones: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Robot {
-  pub robots: Vec<Robot>,
   pub x: usize,
   pub y: usize,
   pub inventory: HashMap<ResourceType, u32>,
-  pub inventory_capacity: usize,
+  pub hold: CargoHold,
   pub collected_science_positions: Vec<(usize, usize)>,
   pub robot_type: RobotType,
+  pub automated: bool,
+  pub task: RobotTask,
+  // Ordre de déplacement donné à la souris, conservé jusqu'à arrivée
+  pub order: Option<(usize, usize)>,
+  pub speed: u32,
+  // Points de mouvement restants ; négatif si le dernier pas a été pris à crédit
+  pub move_points: i32,
+  pub telemetry: RobotTelemetry,
 }
 
 impl Robot {
+  pub fn new(robot_type: RobotType, x: usize, y: usize) -> Self {
+    Robot {
+      x,
+      y,
+      inventory: HashMap::new(),
+      hold: robot_type.cargo_hold(),
+      collected_science_positions: Vec::new(),
+      robot_type,
+      automated: true,
+      task: RobotTask::Idle,
+      order: None,
+      speed: BASE_SPEED,
+      move_points: 0,
+      telemetry: RobotTelemetry::default(),
+    }
+  }
+
   pub fn inventory_count(&self) -> usize {
     self.inventory.values().sum::<u32>() as usize
   }
 
-  pub fn collect_resource(&mut self, map: &mut Map, resources_revealed: bool) -> Option<String> {
-    let current_cell = map.grid[self.y][self.x];
-    let is_accessible = map.is_resource_accessible(self.x, self.y);
+  // Charge de la soute, chaque unité comptée selon son poids
+  pub fn load(&self) -> u32 {
+    self
+      .inventory
+      .iter()
+      .map(|(res, qty)| res.weight() * qty)
+      .sum()
+  }
 
-    match current_cell {
-      Cell::Science => {
-        let count = self.inventory.entry(ResourceType::Science).or_insert(0);
-        *count += 1;
+  pub fn can_carry(&self, res: &ResourceType) -> Result<(), CollectError> {
+    if !self.hold.accepts.contains(res) {
+      return Err(CollectError::NotAccepted);
+    }
+    if self.load() + res.weight() > self.hold.capacity {
+      return Err(CollectError::Full);
+    }
+    Ok(())
+  }
 
-        self.collected_science_positions.push((self.x, self.y));
-        map.grid[self.y][self.x] = Cell::Empty;
+  // Plus rien de ce que la soute accepte n'y tient
+  pub fn is_full(&self) -> bool {
+    self
+      .hold
+      .accepts
+      .iter()
+      .all(|res| self.can_carry(res).is_err())
+  }
 
-        return Some("Lieu scientifique collecté ! Retourne au labo.".to_string());
-      }
-      Cell::Mineral if resources_revealed || is_accessible => {
-        let count = self.inventory.entry(ResourceType::Mineral).or_insert(0);
-        *count += 1;
-        map.grid[self.y][self.x] = Cell::Empty;
+  // Ramasse la ressource sous le robot, ou la laisse en place en expliquant pourquoi
+  pub fn collect_resource(
+    &mut self,
+    map: &mut Map,
+    resources_revealed: bool,
+  ) -> Result<ResourceType, CollectError> {
+    let res = match map.grid()[(self.x, self.y)] {
+      Cell::Mineral => ResourceType::Mineral,
+      Cell::Energy => ResourceType::Energy,
+      Cell::Science => ResourceType::Science,
+      _ => return Err(CollectError::Nothing),
+    };
+    // La science se ramasse partout, c'est elle qui débloque les zones
+    if res != ResourceType::Science
+      && !resources_revealed
+      && !map.is_resource_accessible(self.x, self.y)
+    {
+      return Err(CollectError::Locked);
+    }
+    self.can_carry(&res)?;
 
-        map.update_zone_resource_counts();
+    *self.inventory.entry(res.clone()).or_insert(0) += 1;
+    if res == ResourceType::Science {
+      self.collected_science_positions.push((self.x, self.y));
+    }
+    map.set_cell(self.x, self.y, Cell::Empty);
+    Ok(res)
+  }
 
-        None
-      }
-      Cell::Energy if resources_revealed || is_accessible => {
-        let count = self.inventory.entry(ResourceType::Energy).or_insert(0);
-        *count += 1;
-        map.grid[self.y][self.x] = Cell::Empty;
-        map.update_zone_resource_counts();
+  pub fn unload_resources(&mut self, station: &mut Station, map: &mut Map) -> Unloaded {
+    let mut unloaded = Unloaded::default();
+    if self.x != station.x || self.y != station.y || self.inventory.is_empty() {
+      return unloaded;
+    }
 
-        None
+    for (res, qty) in self.inventory.drain() {
+      if let ResourceType::Science = res {
+        unloaded.science = true;
       }
-      _ => None,
+      *station.inventory.entry(res).or_insert(0) += qty;
     }
-  }
 
-  pub fn unload_resources(&mut self, station: &mut Station, map: &mut Map) -> bool {
-    if self.x == station.x && self.y == station.y {
-      if self.inventory.is_empty() {
-        println!("Aucune ressource à décharger !");
-        return false;
-      } else {
-        let mut science_deposited = false;
-
-        for (res, qty) in self.inventory.drain() {
-          if let ResourceType::Science = res {
-            science_deposited = true;
-          }
-          *station.inventory.entry(res).or_insert(0) += qty;
-        }
-
-        if science_deposited && !self.collected_science_positions.is_empty() {
-          for &science_pos in &self.collected_science_positions {
-            map.unlock_zone_with_science(science_pos);
-          }
-
-          self.collected_science_positions.clear();
-        }
-        map.next_turn();
-        return science_deposited;
-      }
-    } else {
-      false
+    // Chaque science déposée débloque la zone où elle a été ramassée
+    for science_pos in self.collected_science_positions.drain(..) {
+      unloaded
+        .zones
+        .extend(map.unlock_zone_with_science(science_pos));
     }
+    map.next_turn();
+    unloaded
   }
 
+  // Renvoie la raison du refus quand le déplacement est impossible
   pub fn try_move(
     &mut self,
     dx: isize,
@@ -104,42 +216,193 @@ impl Robot {
     map: &Map,
     resources_revealed: bool,
     other_robots: &[(usize, usize)],
-    station: &Station, // Ajoute ce paramètre
-  ) {
+    stations: &[Station],
+  ) -> Result<(), MoveError> {
     let new_x = (self.x as isize) + dx;
     let new_y = (self.y as isize) + dy;
+    if !map.grid().contains(new_x, new_y) {
+      return Err(MoveError::OutOfMap);
+    }
+    let (new_x, new_y) = (new_x as usize, new_y as usize);
 
-    if new_x >= 0 && new_y >= 0 && (new_x as usize) < map.width && (new_y as usize) < map.height {
-      // Vérifie la collision avec les autres robots
-      let is_station = new_x as usize == station.x && new_y as usize == station.y;
-      if !is_station
-        && other_robots
-          .iter()
-          .any(|(x, y)| *x == new_x as usize && *y == new_y as usize)
-      {
-        // On n'autorise le stacking que sur la station
-        println!("Déplacement impossible : un autre robot occupe déjà cette case !");
-        return;
-      }
-
-      let target_cell = map.grid[new_y as usize][new_x as usize];
-      let is_accessible = map.is_resource_accessible(new_x as usize, new_y as usize);
+    // Vérifie la collision avec les autres robots
+    let is_station = stations.iter().any(|s| s.x == new_x && s.y == new_y);
+    if !is_station && other_robots.contains(&(new_x, new_y)) {
+      // On n'autorise le stacking que sur la station
+      return Err(MoveError::Occupied);
+    }
 
-      if !resources_revealed
-        && !is_accessible
-        && (target_cell == Cell::Mineral || target_cell == Cell::Energy)
-      {
-        return;
-      }
+    let target_cell = map.grid()[(new_x, new_y)];
+    if !resources_revealed
+      && !map.is_resource_accessible(new_x, new_y)
+      && (target_cell == Cell::Mineral || target_cell == Cell::Energy)
+    {
+      return Err(MoveError::Hidden);
+    }
 
-      if target_cell != Cell::Wall && target_cell != Cell::Obstacle {
-        self.x = new_x as usize;
-        self.y = new_y as usize;
-      } else {
-        println!("Déplacement impossible !");
-      }
+    if target_cell == Cell::Wall || target_cell == Cell::Obstacle {
+      return Err(MoveError::Blocked);
     }
+    self.x = new_x;
+    self.y = new_y;
+    self.telemetry.distance += 1;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Zones de 3x2 cases : (0-2, 0-1), (3-5, 0-1), (0-2, 2-3), (3-5, 2-3)
+  fn map() -> Map {
+    Map::from_rows(&["OOOOOO", "O.SM.O", "O.MEOO", "OOOOOO"])
   }
 
+  #[test]
+  fn try_move_refuses_walls_edges_and_hidden_resources() {
+    let map = map();
+    let mut robot = Robot::new(RobotType::Collector, 1, 1);
+    assert_eq!(
+      robot.try_move(0, -1, &map, false, &[], &[]),
+      Err(MoveError::Blocked)
+    );
+    assert_eq!(
+      robot.try_move(1, 0, &map, false, &[], &[]),
+      Ok(()),
+      "la science n'est jamais cachée"
+    );
+    assert_eq!(
+      robot.try_move(1, 0, &map, false, &[], &[]),
+      Err(MoveError::Hidden)
+    );
+    assert_eq!(robot.try_move(1, 0, &map, true, &[], &[]), Ok(()));
+    assert_eq!((robot.x, robot.y), (3, 1));
+    assert_eq!(robot.telemetry.distance, 2);
 
+    let mut corner = Robot::new(RobotType::Collector, 0, 0);
+    assert_eq!(
+      corner.try_move(-1, 0, &map, true, &[], &[]),
+      Err(MoveError::OutOfMap)
+    );
+  }
+
+  #[test]
+  fn robots_only_stack_on_stations() {
+    let map = map();
+    let others = [(2, 1), (1, 2)];
+    let stations = [Station::new(2, 1)];
+    let mut robot = Robot::new(RobotType::Collector, 1, 1);
+    assert_eq!(
+      robot.try_move(0, 1, &map, true, &others, &stations),
+      Err(MoveError::Occupied)
+    );
+    assert_eq!(robot.try_move(1, 0, &map, true, &others, &stations), Ok(()));
+    assert_eq!((robot.x, robot.y), (2, 1));
+  }
+
+  #[test]
+  fn collect_respects_locked_zones() {
+    let mut map = map();
+    let mut robot = Robot::new(RobotType::Collector, 3, 1);
+    assert_eq!(
+      robot.collect_resource(&mut map, false),
+      Err(CollectError::Locked)
+    );
+    assert!(robot.inventory.is_empty());
+    assert_eq!(map.grid()[(3, 1)], Cell::Mineral);
+
+    map.unlock_zone(1);
+    assert_eq!(
+      robot.collect_resource(&mut map, false),
+      Ok(ResourceType::Mineral)
+    );
+    assert_eq!(robot.inventory.get(&ResourceType::Mineral), Some(&1));
+    assert_eq!(map.grid()[(3, 1)], Cell::Empty);
+    assert_eq!(map.zones[1].resource_count, (0, 0));
+
+    // Une fois révélées, les ressources se ramassent partout
+    robot.x = 2;
+    robot.y = 2;
+    assert_eq!(
+      robot.collect_resource(&mut map, true),
+      Ok(ResourceType::Mineral)
+    );
+    assert_eq!(robot.inventory.get(&ResourceType::Mineral), Some(&2));
+    robot.x = 1;
+    assert_eq!(
+      robot.collect_resource(&mut map, true),
+      Err(CollectError::Nothing)
+    );
+  }
+
+  #[test]
+  fn full_robots_leave_resources_in_place() {
+    let mut map = map();
+    let mut robot = Robot::new(RobotType::Collector, 2, 2);
+    robot.hold.capacity = 1;
+    assert!(robot.collect_resource(&mut map, true).is_ok());
+    assert!(robot.is_full());
+    robot.x = 3;
+    assert_eq!(
+      robot.collect_resource(&mut map, true),
+      Err(CollectError::Full)
+    );
+    assert_eq!(robot.load(), 1);
+    assert_eq!(map.grid()[(3, 2)], Cell::Energy);
+  }
+
+  #[test]
+  fn holds_depend_on_the_robot_type() {
+    let mut map = map();
+    let mut explorator = Robot::new(RobotType::Explorator, 3, 1);
+    assert_eq!(
+      explorator.collect_resource(&mut map, true),
+      Err(CollectError::NotAccepted)
+    );
+    assert_eq!(map.grid()[(3, 1)], Cell::Mineral);
+
+    // Deux échantillons de science remplissent la soute d'un explorateur
+    explorator.inventory.insert(ResourceType::Science, 2);
+    assert_eq!(explorator.load(), 6);
+    assert!(explorator.is_full());
+    assert_eq!(
+      explorator.can_carry(&ResourceType::Science),
+      Err(CollectError::Full)
+    );
+
+    let builder = Robot::new(RobotType::Builder, 2, 1);
+    assert!(builder.is_full());
+    assert_eq!(
+      builder.can_carry(&ResourceType::Science),
+      Err(CollectError::NotAccepted)
+    );
+  }
+
+  #[test]
+  fn unloading_science_unlocks_its_zone() {
+    let mut map = map();
+    let mut station = Station::new(1, 1);
+    let mut robot = Robot::new(RobotType::Explorator, 2, 1);
+    assert_eq!(
+      robot.collect_resource(&mut map, false),
+      Ok(ResourceType::Science)
+    );
+    assert_eq!(robot.collected_science_positions, vec![(2, 1)]);
+
+    assert_eq!(
+      robot.unload_resources(&mut station, &mut map),
+      Unloaded::default(),
+      "loin de la station"
+    );
+    robot.x = 1;
+    let unloaded = robot.unload_resources(&mut station, &mut map);
+    assert!(unloaded.science);
+    assert_eq!(unloaded.zones, vec![0]);
+    assert!(map.zones[0].is_unlocked);
+    assert!(!map.zones[1].is_unlocked);
+    assert_eq!(station.amount(&ResourceType::Science), 1);
+    assert!(robot.inventory.is_empty());
+    assert!(robot.collected_science_positions.is_empty());
+  }
 }
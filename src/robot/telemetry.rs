@@ -0,0 +1,40 @@
+use crate::game::stats::RobotActivity;
+
+// Au-delà de ce nombre de tours bloqué, le robot abandonne son plan et tente de se dégager
+pub const WATCHDOG_REPLAN_TICKS: u32 = 10;
+// Au-delà de celui-ci, il est signalé au joueur comme ayant besoin d'aide
+pub const WATCHDOG_HELP_TICKS: u32 = 30;
+
+#[derive(Debug, Default, Clone)]
+pub struct RobotTelemetry {
+  pub distance: u64,
+  pub ticks_idle: u64,
+  pub ticks_blocked: u64,
+  pub delivered: u64,
+  pub blocked_streak: u32,
+}
+
+impl RobotTelemetry {
+  pub fn record(&mut self, activity: RobotActivity, delivered: u32) {
+    self.delivered += delivered as u64;
+    match activity {
+      RobotActivity::Idle => self.ticks_idle += 1,
+      RobotActivity::Blocked => self.ticks_blocked += 1,
+      _ => {}
+    }
+
+    if activity == RobotActivity::Blocked {
+      self.blocked_streak += 1;
+    } else {
+      self.blocked_streak = 0;
+    }
+  }
+
+  pub fn needs_replan(&self) -> bool {
+    self.blocked_streak > 0 && self.blocked_streak.is_multiple_of(WATCHDOG_REPLAN_TICKS)
+  }
+
+  pub fn needs_help(&self) -> bool {
+    self.blocked_streak >= WATCHDOG_HELP_TICKS
+  }
+}
@@ -1,9 +1,49 @@
-use std::collections::HashMap;
 use crate::robot::robot::ResourceType;
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct Station {
   pub x: usize,
   pub y: usize,
   pub inventory: HashMap<ResourceType, u32>,
+  // Ressources dépensées en constructions depuis le début de la partie
+  pub consumed: HashMap<ResourceType, u32>,
+}
+
+impl Station {
+  pub fn new(x: usize, y: usize) -> Self {
+    Station {
+      x,
+      y,
+      inventory: HashMap::new(),
+      consumed: HashMap::new(),
+    }
+  }
+
+  pub fn amount(&self, res: &ResourceType) -> u32 {
+    self.inventory.get(res).copied().unwrap_or(0)
+  }
+
+  // Dépense pour une construction : comme `spend`, mais les unités sont comptées comme consommées
+  pub fn consume(&mut self, res: ResourceType, qty: u32) -> bool {
+    if !self.spend(res.clone(), qty) {
+      return false;
+    }
+    *self.consumed.entry(res).or_insert(0) += qty;
+    true
+  }
+
+  // Retire `qty` unités du stock, ou ne fait rien si la station n'en a pas assez
+  pub fn spend(&mut self, res: ResourceType, qty: u32) -> bool {
+    match self.inventory.get_mut(&res) {
+      Some(stock) if *stock >= qty => {
+        *stock -= qty;
+        if *stock == 0 {
+          self.inventory.remove(&res);
+        }
+        true
+      }
+      _ => qty == 0,
+    }
+  }
 }
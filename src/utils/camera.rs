@@ -0,0 +1,95 @@
+use crate::map::map::Map;
+
+pub const ZOOM_LEVELS: [usize; 3] = [1, 2, 4];
+// Lignes du terminal laissées aux panneaux sous la carte
+const RESERVED_ROWS: usize = 12;
+const MIN_VIEW_ROWS: usize = 8;
+
+// Portion de la carte affichée : coin haut-gauche en cases, taille en symboles
+#[derive(Debug)]
+pub struct Camera {
+  pub x: usize,
+  pub y: usize,
+  pub width: usize,
+  pub height: usize,
+  pub zoom: usize,
+  pub follow: bool,
+  // Glisser-déposer à la souris : dernière position et déplacement effectif
+  pub drag_from: Option<(u16, u16)>,
+  pub dragged: bool,
+}
+
+impl Default for Camera {
+  fn default() -> Self {
+    Camera {
+      x: 0,
+      y: 0,
+      width: 40,
+      height: 20,
+      zoom: 1,
+      follow: false,
+      drag_from: None,
+      dragged: false,
+    }
+  }
+}
+
+impl Camera {
+  // Adapte la vue à la taille du terminal, chaque symbole occupant deux colonnes
+  pub fn resize(&mut self, columns: u16, rows: u16, map: &Map) {
+    let rows = (rows as usize)
+      .saturating_sub(RESERVED_ROWS)
+      .max(MIN_VIEW_ROWS);
+    self.width = ((columns as usize) / 2).max(1);
+    self.height = rows;
+    self.clamp(map);
+  }
+
+  // Nombre de cases de la carte couvertes par la vue, en largeur et hauteur
+  pub fn span(&self, map: &Map) -> (usize, usize) {
+    (
+      (self.width * self.zoom).min(map.width),
+      (self.height * self.zoom).min(map.height),
+    )
+  }
+
+  pub fn clamp(&mut self, map: &Map) {
+    let (span_w, span_h) = self.span(map);
+    self.x = self.x.min(map.width - span_w);
+    self.y = self.y.min(map.height - span_h);
+  }
+
+  pub fn scroll(&mut self, dx: isize, dy: isize, map: &Map) {
+    self.x = self.x.saturating_add_signed(dx * self.zoom as isize);
+    self.y = self.y.saturating_add_signed(dy * self.zoom as isize);
+    self.clamp(map);
+  }
+
+  pub fn center_on(&mut self, x: usize, y: usize, map: &Map) {
+    let (span_w, span_h) = self.span(map);
+    self.x = x.saturating_sub(span_w / 2);
+    self.y = y.saturating_sub(span_h / 2);
+    self.clamp(map);
+  }
+
+  pub fn cycle_zoom(&mut self, map: &Map) {
+    let index = ZOOM_LEVELS
+      .iter()
+      .position(|z| *z == self.zoom)
+      .unwrap_or(0);
+    self.zoom = ZOOM_LEVELS[(index + 1) % ZOOM_LEVELS.len()];
+    self.clamp(map);
+  }
+
+  pub fn contains(&self, x: usize, y: usize, map: &Map) -> bool {
+    let (span_w, span_h) = self.span(map);
+    x >= self.x && y >= self.y && x < self.x + span_w && y < self.y + span_h
+  }
+
+  // La carte est affichée en haut à gauche de l'écran
+  pub fn screen_to_cell(&self, column: u16, row: u16, map: &Map) -> Option<(usize, usize)> {
+    let x = self.x + (column as usize / 2) * self.zoom;
+    let y = self.y + row as usize * self.zoom;
+    self.contains(x, y, map).then_some((x, y))
+  }
+}
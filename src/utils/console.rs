@@ -0,0 +1,70 @@
+use crate::GameState;
+use crate::game::command::{apply_command, complete, parse_command};
+use crate::utils::front_end::FrontEnd;
+use crate::utils::locale::Msg;
+use crossterm::event::KeyCode;
+
+// Ligne de commande affichée sous la carte
+#[derive(Debug, Default)]
+pub struct Console {
+  pub open: bool,
+  pub input: String,
+  pub history: Vec<String>,
+  history_pos: Option<usize>,
+}
+
+impl Console {
+  pub fn open(&mut self) {
+    self.open = true;
+    self.input.clear();
+    self.history_pos = None;
+  }
+
+  fn browse_history(&mut self, older: bool) {
+    if self.history.is_empty() {
+      return;
+    }
+    let last = self.history.len() - 1;
+    self.history_pos = match (self.history_pos, older) {
+      (None, true) => Some(last),
+      (None, false) => None,
+      (Some(pos), true) => Some(pos.saturating_sub(1)),
+      (Some(pos), false) if pos < last => Some(pos + 1),
+      (Some(_), false) => None,
+    };
+    self.input = self
+      .history_pos
+      .map(|pos| self.history[pos].clone())
+      .unwrap_or_default();
+  }
+}
+
+pub fn handle_console_key(state: &mut GameState, front: &mut FrontEnd, code: KeyCode) {
+  match code {
+    KeyCode::Esc => front.console.open = false,
+    KeyCode::Char(c) => front.console.input.push(c),
+    KeyCode::Backspace => {
+      front.console.input.pop();
+    }
+    KeyCode::Up => front.console.browse_history(true),
+    KeyCode::Down => front.console.browse_history(false),
+    KeyCode::Tab => {
+      let zone_names: Vec<String> = state.map.zones.iter().map(|z| z.name.clone()).collect();
+      front.console.input = complete(&front.console.input, &zone_names);
+    }
+    KeyCode::Enter => {
+      let input = front.console.input.trim().to_string();
+      front.console.open = false;
+      if input.is_empty() {
+        return;
+      }
+
+      let result =
+        parse_command(&input).and_then(|command| apply_command(state, command, &front.theme));
+      state.last_collect_message =
+        Some(result.unwrap_or_else(|e| Msg::Error(&e.to_string()).to_string()));
+      front.console.history.push(input);
+    }
+    _ => {}
+  }
+}
@@ -1,24 +1,93 @@
-use crate::Map;
-use crate::robot::robot::ResourceType;
+use crate::game::clock::SimClock;
+use crate::map::cell::Cell;
+use crate::robot::robot::{ResourceType, Robot, RobotType};
 use crate::station::station::Station;
+use crate::utils::camera::Camera;
+use crate::utils::locale::{KEY_HELP_LINES, Msg};
+use crate::utils::theme::Theme;
+use crate::{GameState, Map};
+use std::collections::VecDeque;
 
-pub fn print_commands_and_indicators() {
-  println!("╔══════════════════════════════╗ ╔══════════════════════════════╗");
-  println!("║          COMMANDES           ║ ║       INDICATEURS            ║");
-  println!("╠══════════════════════════════╣ ╠══════════════════════════════╣");
-  println!("║ ↑ ↓ ← →  : Déplacer robot    ║ ║ 💎 Mineral                   ║");
-  println!("║ u        : Décharger         ║ ║ ⚡ Energy                    ║");
-  println!("║ a        : Automatiser       ║ ║ 🧪 Science                   ║");
-  println!("║ ESC      : Quitter           ║ ║ 🤖 Collecteur                ║");
-  println!("╚══════════════════════════════╝ ║ 👽 Explorateur               ║");
-  println!("                                 ║ 🏭 Station                   ║");
-  println!("                                 ╚══════════════════════════════╝");
+// Largeur intérieure des encadrés
+const BOX_WIDTH: usize = 30;
+
+fn centered(title: &str) -> String {
+  format!("{title:^BOX_WIDTH$}")
+}
+
+fn print_title(title: Msg) {
+  println!("╔{}╗", "═".repeat(BOX_WIDTH));
+  println!("║{}║", centered(&title.to_string()));
+  println!("╚{}╝", "═".repeat(BOX_WIDTH));
+}
+
+pub fn print_commands_and_indicators(theme: &Theme) {
+  let legend = [
+    (
+      theme.cell(Cell::Mineral, true),
+      Msg::Resource(&ResourceType::Mineral),
+    ),
+    (
+      theme.cell(Cell::Energy, true),
+      Msg::Resource(&ResourceType::Energy),
+    ),
+    (
+      theme.cell(Cell::Science, true),
+      Msg::Resource(&ResourceType::Science),
+    ),
+    (
+      theme.robot(RobotType::Collector),
+      Msg::RobotKind(RobotType::Collector),
+    ),
+    (
+      theme.robot(RobotType::Explorator),
+      Msg::RobotKind(RobotType::Explorator),
+    ),
+    (
+      theme.robot(RobotType::Builder),
+      Msg::RobotKind(RobotType::Builder),
+    ),
+    (theme.station(), Msg::Station),
+    (theme.cell(Cell::Road, true), Msg::CellKind(Cell::Road)),
+  ];
+  let line = "═".repeat(BOX_WIDTH);
+
+  println!("╔{line}╗ ╔{line}╗");
+  println!(
+    "║{}║ ║{}║",
+    centered(&Msg::CommandsTitle.to_string()),
+    centered(&Msg::LegendTitle.to_string())
+  );
+  println!("╠{line}╣ ╠{line}╣");
+  for i in 0..KEY_HELP_LINES {
+    let command = format!(" {}", Msg::KeyHelp(i));
+    match legend.get(i) {
+      Some((symbol, label)) => {
+        println!("║{command}║ ║ {symbol} {:<26}║", label.to_string())
+      }
+      None if i == legend.len() => println!("║{command}║ ╚{line}╝"),
+      None => println!("║{command}║"),
+    }
+  }
+  println!("╚{line}╝");
   println!();
 }
 
-pub fn print_map_stats(map: &Map, robot_speed_ms: u64, station: &Station) {
-  let speed_sec = robot_speed_ms as f64 / 1000.0;
-  let speed_kmh = 3.6 / speed_sec; // Conversion en km/h (assumant 1 case = 1m)
+pub fn print_camera(camera: &Camera, map: &Map, theme: &Theme) {
+  let (span_w, span_h) = camera.span(map);
+  println!(
+    "{}{}",
+    theme.icon("🎥"),
+    Msg::CameraView {
+      from: (camera.x, camera.y),
+      to: (camera.x + span_w - 1, camera.y + span_h - 1),
+      zoom: camera.zoom,
+      follow: camera.follow,
+    }
+  );
+}
+
+pub fn print_map_stats(map: &Map, clock: &SimClock, stations: &[Station], theme: &Theme) {
   let unlocked_zones: Vec<&str> = map
     .zones
     .iter()
@@ -26,48 +95,235 @@ pub fn print_map_stats(map: &Map, robot_speed_ms: u64, station: &Station) {
     .map(|z| z.name.as_str())
     .collect();
 
-  
-  println!("╔══════════════════════════════╗");
-  println!("║       INFO DE LA MAP         ║");
-  println!("╚══════════════════════════════╝");
-  
+  print_title(Msg::MapInfoTitle);
+
   println!(
-    "🗾 Map size: {}x{} | Tour: {}",
-    map.width, map.height, map.current_turn
+    "{}{}",
+    theme.icon("🗾"),
+    Msg::MapSize {
+      width: map.width,
+      height: map.height,
+      turn: map.current_turn,
+    }
   );
-  
+
   let (unlocked, total, percentage) = map.zone_stats();
   let (minerals, energies) = map.accessible_resources();
-  
+
+  println!(
+    "{}{}",
+    theme.icon("🌍"),
+    Msg::ZonesExplored {
+      unlocked,
+      total,
+      percentage,
+    }
+  );
+  println!(
+    "{} {}",
+    theme.resource(&ResourceType::Mineral),
+    Msg::AccessibleMinerals(minerals)
+  );
+  println!(
+    "{} {}",
+    theme.resource(&ResourceType::Energy),
+    Msg::AccessibleEnergies(energies)
+  );
+
+  println!();
+
+  print_title(Msg::StationsTitle);
+
+  for (i, station) in stations.iter().enumerate() {
+    if i == 0 {
+      println!(
+        "{}{}",
+        theme.icon("🏭"),
+        Msg::MainStation(station.x, station.y)
+      );
+    } else {
+      println!(
+        "{}{}",
+        theme.icon("🏭"),
+        Msg::Outpost(i, station.x, station.y)
+      );
+    }
+
+    if station.inventory.is_empty() {
+      println!("{}", centered(&Msg::NoResources.to_string()));
+    } else {
+      for (res, qty) in &station.inventory {
+        println!("  {} {}", theme.resource(res), Msg::StationStock(res, *qty));
+      }
+    }
+  }
+  println!();
+
+  if clock.paused {
+    println!("{}{}", theme.icon("⏸️"), Msg::Paused);
+  } else {
+    println!(
+      "{}{}",
+      theme.icon("⏱️"),
+      Msg::Running(clock.speed.label(), clock.base_tick_ms)
+    );
+  }
   println!(
-    "🌍 Zones explorées: {}/{} ({:.0}%)",
-    unlocked, total, percentage
+    "{}{}",
+    theme.icon("📍"),
+    Msg::MappedRegions(&unlocked_zones.join(", "))
   );
-  println!("💎 Minerais accessibles: {} ", minerals);
-  println!("⚡ Énergies accessibles: {}", energies);
-  
-  
   println!();
-  
-  println!("╔══════════════════════════════╗");
-  println!("║   INVENTAIRE DE LA STATION   ║");
-  println!("╚══════════════════════════════╝");
-  
-  if station.inventory.is_empty() {
-    println!("       Aucune ressource       ");
+}
+
+pub fn print_construction_info(
+  queue: &VecDeque<(usize, usize)>,
+  suggestions: &[((usize, usize), u32)],
+  theme: &Theme,
+) {
+  let planned: Vec<String> = queue.iter().map(|(x, y)| format!("({x}, {y})")).collect();
+  if planned.is_empty() {
+    println!("{}{}", theme.icon("👷"), Msg::NoBuildSites);
   } else {
-    for (res, qty) in &station.inventory {
-      let icon = match res {
-        ResourceType::Mineral => "💎",
-        ResourceType::Energy => "⚡",
-        ResourceType::Science => "🧪",
-      };
-      println!("  {} {:?} : {} unités", icon, res, qty);
+    println!(
+      "{}{}",
+      theme.icon("👷"),
+      Msg::BuildSites(&planned.join(" → "))
+    );
+  }
+
+  if let Some(((x, y), saved)) = suggestions.first() {
+    println!(
+      "{}{}",
+      theme.icon("🛣️"),
+      Msg::SuggestedShortcut(*x, *y, *saved)
+    );
+  }
+  println!();
+}
+
+pub fn print_selected_robot(
+  index: Option<usize>,
+  robot: Option<&Robot>,
+  clock: &SimClock,
+  theme: &Theme,
+) {
+  let (Some(index), Some(robot)) = (index, robot) else {
+    println!("{}", Msg::NoRobotSelected);
+    return;
+  };
+
+  let amount = |res: &ResourceType| robot.inventory.get(res).copied().unwrap_or(0);
+
+  print_title(Msg::SelectedRobotTitle);
+  println!(
+    "{} {} {}",
+    theme.robot(robot.robot_type),
+    Msg::RobotKind(robot.robot_type),
+    Msg::RobotHeader {
+      index: index + 1,
+      x: robot.x,
+      y: robot.y,
+      automated: robot.automated,
     }
+  );
+  println!("{}{}", theme.icon("📋"), Msg::TaskLine(robot.task));
+
+  // Conversion en km/h (assumant 1 case = 1m), hors routes
+  let steps_per_tick = robot.speed as f64 / Cell::Empty.move_cost() as f64;
+  let tick_sec = clock.tick_duration().as_secs_f64();
+  if tick_sec > 0.0 {
+    println!(
+      "{}{}",
+      theme.icon("🤖"),
+      Msg::SpeedKmh(3.6 * steps_per_tick / tick_sec)
+    );
+  } else {
+    println!("{}{}", theme.icon("🤖"), Msg::SpeedCells(steps_per_tick));
+  }
+  println!(
+    "{}{} {} {} {} {} {} {}",
+    theme.icon("🎒"),
+    Msg::Inventory,
+    theme.resource(&ResourceType::Mineral),
+    amount(&ResourceType::Mineral),
+    theme.resource(&ResourceType::Energy),
+    amount(&ResourceType::Energy),
+    theme.resource(&ResourceType::Science),
+    amount(&ResourceType::Science),
+  );
+  if robot.hold.capacity > 0 {
+    let gauge = theme.gauge(robot.load(), robot.hold.capacity, 10);
+    println!(
+      "{}{}",
+      theme.icon("📦"),
+      Msg::Cargo(&gauge, robot.load(), robot.hold.capacity)
+    );
   }
-  println!("");
 
-  println!("🤖 Vitesse du robot: {:.2} km/h", speed_kmh);
-  println!("📍 Régions cartographiées: {}", unlocked_zones.join(", "));
-  println!("");
+  let telemetry = &robot.telemetry;
+  println!(
+    "{}{}",
+    theme.icon("📏"),
+    Msg::Telemetry {
+      distance: telemetry.distance,
+      delivered: telemetry.delivered,
+      idle: telemetry.ticks_idle,
+      blocked: telemetry.ticks_blocked,
+    }
+  );
+  if telemetry.needs_help() {
+    println!(
+      "{}{}",
+      theme.icon("🆘"),
+      Msg::BlockedFor(telemetry.blocked_streak)
+    );
+  }
+  println!();
+}
+
+// Remplissage de la soute de chaque robot qui en a une
+pub fn print_fleet_cargo(robots: &[Robot], theme: &Theme) {
+  let holds: Vec<String> = robots
+    .iter()
+    .enumerate()
+    .filter(|(_, r)| r.hold.capacity > 0)
+    .map(|(i, r)| format!("#{} {}", i + 1, theme.gauge(r.load(), r.hold.capacity, 4)))
+    .collect();
+  if !holds.is_empty() {
+    println!("{}{}", theme.icon("📦"), Msg::FleetCargo(&holds.join("  ")));
+  }
+}
+
+pub fn print_robots_needing_help(robots: &[Robot], theme: &Theme) {
+  let stuck: Vec<String> = robots
+    .iter()
+    .enumerate()
+    .filter(|(_, r)| r.telemetry.needs_help())
+    .map(|(i, r)| format!("#{} ({}, {})", i + 1, r.x, r.y))
+    .collect();
+  if !stuck.is_empty() {
+    println!(
+      "{}{}",
+      theme.icon("🆘"),
+      Msg::RobotsStuck(&stuck.join(", "))
+    );
+  }
+}
+
+pub fn describe_cell(state: &GameState, x: usize, y: usize, theme: &Theme) -> String {
+  let cell = Msg::CellDescription(x, y, state.map.grid()[(x, y)]);
+
+  match state.map.get_zone_at(x, y) {
+    Some(zone) => format!(
+      "{cell} | {} | {} {} {} {} {}",
+      Msg::ZoneDescription(&zone.name, zone.is_unlocked),
+      theme.resource(&ResourceType::Mineral),
+      zone.resource_count.0,
+      theme.resource(&ResourceType::Energy),
+      zone.resource_count.1,
+      Msg::Remaining
+    ),
+    None => cell.to_string(),
+  }
 }
@@ -0,0 +1,32 @@
+use crate::robot::robot::Robot;
+use crate::utils::camera::Camera;
+use crate::utils::console::Console;
+use crate::utils::overlay::Overlay;
+use crate::utils::theme::Theme;
+
+// État de l'interface terminal : ce qui est affiché et sélectionné, sans effet sur la simulation
+pub struct FrontEnd {
+  pub console: Console,
+  pub camera: Camera,
+  pub theme: Theme,
+  pub overlay: Overlay,
+  pub show_stats: bool,
+  pub selected_robot: Option<usize>,
+}
+
+impl FrontEnd {
+  pub fn new(theme: Theme) -> Self {
+    Self {
+      console: Console::default(),
+      camera: Camera::default(),
+      theme,
+      overlay: Overlay::default(),
+      show_stats: false,
+      selected_robot: Some(0),
+    }
+  }
+
+  pub fn selected<'a>(&self, robots: &'a [Robot]) -> Option<&'a Robot> {
+    self.selected_robot.and_then(|i| robots.get(i))
+  }
+}
@@ -1,78 +1,186 @@
 use crate::GameState;
-use crossterm::{
-  event::{self, Event, KeyCode, KeyEventKind},
-  terminal::{disable_raw_mode},
-};
+use crate::game::clock::SimSpeed;
+use crate::game::construction::suggest_shortcuts;
+use crate::game::outposts::{build_outpost, consolidate_to_main};
+use crate::utils::console::handle_console_key;
+use crate::utils::front_end::FrontEnd;
+use crate::utils::locale::Msg;
+use crate::utils::mouse::handle_mouse_event;
+use crate::utils::terminal::interrupt;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use std::io;
 use std::time::Duration;
 
+const STATS_CSV: &str = "stats.csv";
+
 pub fn handle_keyboard_events(
   state: &mut GameState,
+  front: &mut FrontEnd,
   automation_enabled: &mut bool,
-) -> Result<bool, Box<dyn std::error::Error>> {
-  if event::poll(Duration::from_millis(200))? {
-    if let Event::Key(key_event) = event::read()? {
-      if key_event.kind == KeyEventKind::Press {
-        let robot_positions: Vec<(usize, usize)> =
-          state.robots.iter().map(|r| (r.x, r.y)).collect();
-        let other_robots: Vec<(usize, usize)> = robot_positions
-          .iter()
-          .enumerate()
-          .filter(|(j, _)| *j != 0)
-          .map(|(_, pos)| *pos)
-          .collect();
+  timeout: Duration,
+) -> io::Result<bool> {
+  if !event::poll(timeout)? {
+    return Ok(false);
+  }
 
-        match key_event.code {
-          KeyCode::Up => {
-            state.robots[0].try_move(
-              0,
-              -1,
-              &state.map,
-              state.resources_revealed,
-              &other_robots,
-              &state.station,
-            );
-          }
-          KeyCode::Down => {
-            state.robots[0].try_move(
-              0,
-              1,
-              &state.map,
-              state.resources_revealed,
-              &other_robots,
-              &state.station,
-            );
-          }
-          KeyCode::Left => {
-            state.robots[0].try_move(
-              -1,
-              0,
-              &state.map,
-              state.resources_revealed,
-              &other_robots,
-              &state.station,
-            );
-          }
-          KeyCode::Right => {
-            state.robots[0].try_move(
-              1,
-              0,
-              &state.map,
-              state.resources_revealed,
-              &other_robots,
-              &state.station,
-            );
-          }
-          KeyCode::Char('a' | 'A') => {
-            *automation_enabled = !*automation_enabled;
-          }
-          KeyCode::Esc => {
-            disable_raw_mode()?;
-            println!("Arrêt du programme.");
-            return Ok(true);
+  let event = event::read()?;
+  if let Event::Mouse(mouse_event) = event {
+    handle_mouse_event(state, front, mouse_event);
+    return Ok(false);
+  }
+
+  if let Event::Key(key_event) = event
+    && key_event.kind == KeyEventKind::Press
+  {
+    if key_event.code == KeyCode::Char('c') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+      interrupt();
+      return Ok(true);
+    }
+
+    // La console capture toutes les touches tant qu'elle est ouverte
+    if front.console.open {
+      handle_console_key(state, front, key_event.code);
+      return Ok(false);
+    }
+
+    match key_event.code {
+      KeyCode::Char(':') => {
+        front.console.open();
+      }
+      KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+        let (dx, dy) = match key_event.code {
+          KeyCode::Up => (0, -1),
+          KeyCode::Down => (0, 1),
+          KeyCode::Left => (-1, 0),
+          _ => (1, 0),
+        };
+        // Maj + flèche, ou sans robot sélectionné : fait défiler la vue
+        if key_event.modifiers.contains(KeyModifiers::SHIFT) || front.selected_robot.is_none() {
+          front.camera.follow = false;
+          front.camera.scroll(dx, dy, &state.map);
+        } else if let Some(i) = front.selected_robot {
+          // Déplace le robot sélectionné
+          let other_robots = state.other_robot_positions(i);
+          if let Err(e) = state.robots[i].try_move(
+            dx,
+            dy,
+            &state.map,
+            state.resources_revealed,
+            &other_robots,
+            &state.stations,
+          ) {
+            state.last_collect_message = Some(e.to_string());
           }
-          _ => {}
+          state.map.record_visit(state.robots[i].x, state.robots[i].y);
         }
       }
+      KeyCode::Char(c @ ('w' | 'a' | 's' | 'd' | 'W' | 'A' | 'S' | 'D')) => {
+        // WASD fait défiler la vue, comme Maj + flèche
+        let (dx, dy) = match c.to_ascii_lowercase() {
+          'w' => (0, -1),
+          's' => (0, 1),
+          'a' => (-1, 0),
+          _ => (1, 0),
+        };
+        front.camera.follow = false;
+        front.camera.scroll(dx, dy, &state.map);
+      }
+      KeyCode::Tab => {
+        let count = state.robots.len();
+        front.selected_robot = Some(front.selected_robot.map_or(0, |i| (i + 1) % count));
+      }
+      KeyCode::BackTab => {
+        let count = state.robots.len();
+        front.selected_robot = Some(
+          front
+            .selected_robot
+            .map_or(count - 1, |i| (i + count - 1) % count),
+        );
+      }
+      KeyCode::Char('0') => {
+        front.selected_robot = None;
+      }
+      KeyCode::Char(c @ '1'..='9') => {
+        let index = c as usize - '1' as usize;
+        if index < state.robots.len() {
+          front.selected_robot = Some(index);
+        }
+      }
+      KeyCode::Char('m' | 'M') => {
+        // Bascule le robot sélectionné entre pilotage manuel et automatique
+        if let Some(i) = front.selected_robot {
+          state.robots[i].automated = !state.robots[i].automated;
+        }
+      }
+      KeyCode::Char(' ') => {
+        state.clock.toggle_pause();
+      }
+      KeyCode::Char('.') => {
+        state.clock.request_step();
+      }
+      KeyCode::F(n @ 1..=4) => {
+        state.clock.set_speed(match n {
+          1 => SimSpeed::X1,
+          2 => SimSpeed::X2,
+          3 => SimSpeed::X4,
+          _ => SimSpeed::Max,
+        });
+      }
+      KeyCode::Char('f' | 'F') => {
+        front.camera.follow = !front.camera.follow;
+      }
+      KeyCode::Char('z' | 'Z') => {
+        front.camera.cycle_zoom(&state.map);
+      }
+      KeyCode::Char('h' | 'H') => {
+        front.overlay = front.overlay.next();
+      }
+      KeyCode::Char('g' | 'G') => {
+        front.show_stats = !front.show_stats;
+      }
+      KeyCode::Char('e' | 'E') => {
+        state.last_collect_message = Some(match state.stats.export_csv(&state.map, STATS_CSV) {
+          Ok(()) => Msg::StatsExported(STATS_CSV).to_string(),
+          Err(e) => Msg::ExportFailed(&e.to_string()).to_string(),
+        });
+      }
+      KeyCode::Char('p' | 'P') => {
+        *automation_enabled = !*automation_enabled;
+      }
+      KeyCode::Char('b' | 'B') => {
+        // Ajoute à la file le meilleur raccourci pas encore planifié
+        let suggestion = suggest_shortcuts(
+          &state.map,
+          &state.stations,
+          state.resources_revealed,
+          state.construction_queue.len() + 1,
+        )
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .find(|pos| !state.construction_queue.contains(pos));
+        if let Some(pos) = suggestion {
+          state.construction_queue.push_back(pos);
+        }
+      }
+      KeyCode::Char('c' | 'C') => {
+        state.construction_queue.clear();
+      }
+      KeyCode::Char('o' | 'O') => {
+        // Construit un avant-poste sous le robot sélectionné
+        if let Some((x, y)) = front.selected(&state.robots).map(|r| (r.x, r.y)) {
+          state.last_collect_message =
+            Some(match build_outpost(&state.map, &mut state.stations, x, y) {
+              Ok(station) => Msg::OutpostBuilt(station, x, y).to_string(),
+              Err(e) => e.to_string(),
+            });
+        }
+      }
+      KeyCode::Char('t' | 'T') => {
+        consolidate_to_main(&mut state.stations);
+        state.last_collect_message = Some(Msg::StocksConsolidated.to_string());
+      }
+      KeyCode::Esc => return Ok(true),
+      _ => {}
     }
   }
   Ok(false)
@@ -0,0 +1,941 @@
+use crate::game::stats::RobotActivity;
+use crate::map::cell::Cell;
+use crate::robot::robot::{ResourceType, RobotTask, RobotType};
+use crate::utils::overlay::Overlay;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Catalogue de tous les textes affichés au joueur : tout nouveau message passe
+// par une variante de `Msg`, traduite ici dans chaque langue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum Lang {
+  #[default]
+  Fr,
+  En,
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(Lang::Fr as u8);
+
+impl Lang {
+  pub fn parse(name: &str) -> Option<Self> {
+    match name {
+      "fr" => Some(Lang::Fr),
+      "en" => Some(Lang::En),
+      _ => None,
+    }
+  }
+
+  // Langue tirée de LANG (ex. en_US.UTF-8), le français par défaut
+  pub fn from_env() -> Self {
+    std::env::var("LANG")
+      .ok()
+      .and_then(|value| Lang::parse(value.get(..2)?))
+      .unwrap_or_default()
+  }
+}
+
+pub fn set_lang(lang: Lang) {
+  CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+  match CURRENT.load(Ordering::Relaxed) {
+    1 => Lang::En,
+    _ => Lang::Fr,
+  }
+}
+
+// Argument attendu par une commande de la console
+#[derive(Debug, Clone, Copy)]
+pub enum Arg {
+  RobotNumber,
+  X,
+  Y,
+  Quantity,
+  Speed,
+  RobotType,
+  Resource,
+  Zone,
+  File,
+  Station,
+}
+
+// Aide des touches : (touche, description), alignées sur des colonnes de largeur fixe
+pub const KEY_HELP_LINES: usize = 19;
+const KEY_WIDTH: usize = 8;
+const KEY_HELP_WIDTH: usize = 18;
+
+const KEY_HELP_FR: [(&str, &str); KEY_HELP_LINES] = [
+  ("↑ ↓ ← →", "Déplacer robot"),
+  ("Tab 1-9", "Choisir robot"),
+  ("m", "Robot manuel/auto"),
+  ("u", "Décharger"),
+  ("p", "Automatiser"),
+  ("b", "Prévoir raccourci"),
+  ("c", "Vider chantiers"),
+  ("o", "Bâtir avant-poste"),
+  ("t", "Rapatrier stocks"),
+  ("Clic", "Infos / Envoyer"),
+  ("Clic dr.", "Annuler l'ordre"),
+  (":", "Console"),
+  ("Espace .", "Pause / Pas à pas"),
+  ("F1-F4", "Vitesse x1 à max"),
+  ("g / e", "Graphes / Export"),
+  ("h", "Calque de la carte"),
+  ("wasd", "Défiler / Maj+↑↓←→"),
+  ("f / z", "Suivre / Zoom"),
+  ("ESC", "Quitter"),
+];
+
+const KEY_HELP_EN: [(&str, &str); KEY_HELP_LINES] = [
+  ("↑ ↓ ← →", "Move robot"),
+  ("Tab 1-9", "Select robot"),
+  ("m", "Manual/auto robot"),
+  ("u", "Unload"),
+  ("p", "Automation"),
+  ("b", "Plan shortcut"),
+  ("c", "Clear build queue"),
+  ("o", "Build outpost"),
+  ("t", "Gather stocks"),
+  ("Click", "Info / Send"),
+  ("R-click", "Cancel order"),
+  (":", "Console"),
+  ("Space .", "Pause / Step"),
+  ("F1-F4", "Speed x1 x2 x4 max"),
+  ("g / e", "Charts / Export"),
+  ("h", "Map overlay"),
+  ("wasd", "Scroll / ⇧ ↑↓←→"),
+  ("f / z", "Follow / Zoom"),
+  ("ESC", "Quit"),
+];
+
+pub enum Msg<'a> {
+  // Noms
+  Resource(&'a ResourceType),
+  RobotKind(RobotType),
+  CellKind(Cell),
+  Station,
+  ZoneName(usize),
+  Task(RobotTask),
+  OverlayName(Overlay),
+  Activity(RobotActivity),
+
+  // Panneaux
+  CommandsTitle,
+  LegendTitle,
+  KeyHelp(usize),
+  MapInfoTitle,
+  StationsTitle,
+  SelectedRobotTitle,
+  CameraView {
+    from: (usize, usize),
+    to: (usize, usize),
+    zoom: usize,
+    follow: bool,
+  },
+  OverlayLine(Overlay),
+  Automation(bool),
+  MapSize {
+    width: usize,
+    height: usize,
+    turn: u32,
+  },
+  ZonesExplored {
+    unlocked: usize,
+    total: usize,
+    percentage: f32,
+  },
+  AccessibleMinerals(u32),
+  AccessibleEnergies(u32),
+  MainStation(usize, usize),
+  Outpost(usize, usize, usize),
+  NoResources,
+  StationStock(&'a ResourceType, u32),
+  Paused,
+  Running(&'a str, u64),
+  MappedRegions(&'a str),
+  NoBuildSites,
+  BuildSites(&'a str),
+  SuggestedShortcut(usize, usize, u32),
+  NoRobotSelected,
+  RobotHeader {
+    index: usize,
+    x: usize,
+    y: usize,
+    automated: bool,
+  },
+  TaskLine(RobotTask),
+  SpeedKmh(f64),
+  SpeedCells(f64),
+  Inventory,
+  Cargo(&'a str, u32, u32),
+  FleetCargo(&'a str),
+  Telemetry {
+    distance: u64,
+    delivered: u64,
+    idle: u64,
+    blocked: u64,
+  },
+  BlockedFor(u32),
+  RobotsStuck(&'a str),
+  CellDescription(usize, usize, Cell),
+  ZoneDescription(&'a str, bool),
+  Remaining,
+
+  // Onglet statistiques
+  InventoryChartTitle(u64),
+  ZoneChartTitle,
+  DeliveriesTitle(u64),
+  UtilisationTitle,
+
+  // Événements
+  ScienceCollected,
+  CellOccupied,
+  MoveBlocked,
+  MoveOutOfMap,
+  HiddenResource,
+  NothingToCollect,
+  ResourceLocked,
+  CargoRefused,
+  CargoFull,
+  ZoneUnlocked(&'a str),
+  ScriptError(&'a str, &'a str),
+  ScriptSingleStep(i64, i64),
+  ScriptNoAction,
+  ScriptBudget(u64),
+  ObstacleDemolished(usize, usize),
+  RoadBuilt(usize, usize),
+  OrderUnreachable(usize, usize),
+  DestinationReached(usize, usize),
+  RobotBlocked {
+    index: usize,
+    ticks: u32,
+    dropped: bool,
+  },
+  OutpostNotBuildable(usize, usize),
+  OutpostTooExpensive(u32, u32),
+  OutpostBuilt(usize, usize, usize),
+  StatsExported(&'a str),
+  ExportFailed(&'a str),
+  StocksConsolidated,
+  StockTransferred(u32, &'a ResourceType, usize, usize),
+  NoSuchStation(usize),
+  SameStation,
+  NotEnoughStock(usize, u32),
+  Quit,
+  Interrupted,
+  RobotSent(usize),
+  OrderCancelled(usize),
+
+  // Console
+  Error(&'a str),
+  MissingArg(Arg),
+  InvalidArg(Arg, &'a str),
+  UnknownRobotType(&'a str),
+  UnknownResource(&'a str),
+  EmptyCommand,
+  RobotsNumberedFromOne,
+  UnknownCommand(&'a str),
+  ExtraArgument(&'a str),
+  OutOfMap(usize, usize),
+  NoSuchRobot(usize),
+  RobotSentTo(usize, usize, usize),
+  ResourcesRevealed(bool),
+  UnknownZone(&'a str),
+  NotPassable(usize, usize),
+  InspectedRobot(usize, &'a str),
+  RobotSpawned(usize, usize, usize),
+  ResourcesGiven(u32, &'a ResourceType),
+  TickInterval(u64),
+  MapSeed(u32),
+  SaveFailed(&'a str),
+  GameSaved(&'a str),
+  NotBuildable(usize, usize),
+  AlreadyQueued(usize, usize),
+  BuildQueued(usize, usize, u32, usize),
+  NotQueued(usize, usize),
+  BuildCancelled(usize, usize),
+  BuildPrioritized(usize, usize),
+  CommandsHelp(&'a str),
+
+  // Tournoi
+  TournamentRuns {
+    runs: usize,
+    threads: usize,
+  },
+  TournamentConfig {
+    width: usize,
+    height: usize,
+    ticks: u64,
+  },
+  TournamentColumns,
+  UnknownStrategy(&'a str),
+  BadSeedRange(&'a str),
+  BadMapSize(&'a str),
+
+  // Ligne de commande
+  MissingOptionValue(&'a str, &'a str),
+  UnknownGlyphSet(&'a str),
+  UnknownTheme(&'a str),
+  UnknownLang(&'a str),
+  UnknownOption(&'a str),
+  ScriptOption(&'a str),
+
+  // Vérification des invariants (--check-invariants)
+  InvariantsBroken(u64, u32),
+  ResourcesNotConserved(&'a str, u32, u32),
+  RobotsStacked(usize, usize, usize, usize),
+  RobotOnBlockedCell(usize, usize, usize),
+  RobotOverCapacity(usize, u32, u32),
+  ZoneCountMismatch(usize, (u32, u32), (u32, u32)),
+
+  // Erreurs fatales
+  MapTooSmall {
+    width: usize,
+    height: usize,
+    min: (usize, usize),
+  },
+  TerminalFailed(&'a str),
+}
+
+// Choisit le texte de la langue courante ; les arguments sont capturés par nom
+macro_rules! by_lang {
+  ($f:expr, $fr:literal, $en:literal) => {
+    match lang() {
+      Lang::Fr => write!($f, $fr),
+      Lang::En => write!($f, $en),
+    }
+  };
+}
+
+impl fmt::Display for Arg {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Arg::RobotNumber => by_lang!(f, "Numéro de robot", "Robot number"),
+      Arg::X => write!(f, "x"),
+      Arg::Y => write!(f, "y"),
+      Arg::Quantity => by_lang!(f, "Quantité", "Quantity"),
+      Arg::Speed => by_lang!(f, "Vitesse", "Speed"),
+      Arg::RobotType => by_lang!(f, "Type de robot", "Robot type"),
+      Arg::Resource => by_lang!(f, "Ressource", "Resource"),
+      Arg::Zone => write!(f, "Zone"),
+      Arg::File => by_lang!(f, "Fichier", "File"),
+      Arg::Station => by_lang!(f, "Numéro de station", "Station number"),
+    }
+  }
+}
+
+impl fmt::Display for Msg<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Msg::Resource(resource) => match resource {
+        ResourceType::Mineral => by_lang!(f, "Minerai", "Mineral"),
+        ResourceType::Energy => by_lang!(f, "Énergie", "Energy"),
+        ResourceType::Science => write!(f, "Science"),
+      },
+      Msg::RobotKind(robot_type) => match robot_type {
+        RobotType::Explorator => by_lang!(f, "Explorateur", "Explorer"),
+        RobotType::Collector => by_lang!(f, "Collecteur", "Collector"),
+        RobotType::Builder => by_lang!(f, "Constructeur", "Builder"),
+      },
+      Msg::CellKind(cell) => match cell {
+        Cell::Wall | Cell::Obstacle => write!(f, "Obstacle"),
+        Cell::Empty => by_lang!(f, "Vide", "Empty"),
+        Cell::Energy => by_lang!(f, "Énergie", "Energy"),
+        Cell::Mineral => by_lang!(f, "Minerai", "Mineral"),
+        Cell::Science => by_lang!(f, "Lieu scientifique", "Science site"),
+        Cell::Road => by_lang!(f, "Route", "Road"),
+      },
+      Msg::Station => write!(f, "Station"),
+      Msg::ZoneName(id) => match id {
+        0 => by_lang!(f, "Nord-Ouest", "North-West"),
+        1 => by_lang!(f, "Nord-Est", "North-East"),
+        2 => by_lang!(f, "Sud-Ouest", "South-West"),
+        3 => by_lang!(f, "Sud-Est", "South-East"),
+        _ => write!(f, "Zone {id}"),
+      },
+      Msg::Task(task) => match task {
+        RobotTask::Idle => by_lang!(f, "En attente", "Idle"),
+        RobotTask::Manual => by_lang!(f, "Pilotage manuel", "Manual control"),
+        RobotTask::Exploring(x, y) => {
+          by_lang!(f, "Explore vers ({x}, {y})", "Exploring towards ({x}, {y})")
+        }
+        RobotTask::Collecting(x, y) => {
+          by_lang!(f, "Collecte vers ({x}, {y})", "Collecting at ({x}, {y})")
+        }
+        RobotTask::Returning(0) => by_lang!(
+          f,
+          "Retour à la station principale",
+          "Returning to the main station"
+        ),
+        RobotTask::Returning(i) => {
+          by_lang!(f, "Retour à l'avant-poste {i}", "Returning to outpost {i}")
+        }
+        RobotTask::Building(x, y) => {
+          by_lang!(f, "Chantier en ({x}, {y})", "Building at ({x}, {y})")
+        }
+        RobotTask::GoingTo(x, y) => {
+          by_lang!(f, "Ordre : aller en ({x}, {y})", "Order: go to ({x}, {y})")
+        }
+      },
+      Msg::OverlayName(overlay) => match overlay {
+        Overlay::None => by_lang!(f, "aucun", "none"),
+        Overlay::Traffic => by_lang!(f, "trafic", "traffic"),
+        Overlay::Resources => by_lang!(f, "densité de ressources", "resource density"),
+        Overlay::Distance => by_lang!(f, "distance aux stations", "distance to stations"),
+        Overlay::Zones => write!(f, "zones"),
+        Overlay::Paths => by_lang!(f, "chemins prévus", "planned paths"),
+      },
+      Msg::Activity(activity) => match activity {
+        RobotActivity::Moving => by_lang!(f, "Mouvement", "Moving"),
+        RobotActivity::Idle => by_lang!(f, "Inactif", "Idle"),
+        RobotActivity::Blocked => by_lang!(f, "Bloqué", "Blocked"),
+        RobotActivity::Unloading => by_lang!(f, "Décharge", "Unloading"),
+      },
+
+      Msg::CommandsTitle => by_lang!(f, "COMMANDES", "COMMANDS"),
+      Msg::LegendTitle => by_lang!(f, "INDICATEURS", "LEGEND"),
+      Msg::KeyHelp(i) => {
+        let table = match lang() {
+          Lang::Fr => &KEY_HELP_FR,
+          Lang::En => &KEY_HELP_EN,
+        };
+        let (key, help) = table.get(i).copied().unwrap_or_default();
+        write!(f, "{key:<KEY_WIDTH$} : {help:<KEY_HELP_WIDTH$}")
+      }
+      Msg::MapInfoTitle => by_lang!(f, "INFO DE LA MAP", "MAP INFO"),
+      Msg::StationsTitle => by_lang!(f, "INVENTAIRE DES STATIONS", "STATION INVENTORY"),
+      Msg::SelectedRobotTitle => by_lang!(f, "ROBOT SÉLECTIONNÉ", "SELECTED ROBOT"),
+      Msg::CameraView {
+        from: (x0, y0),
+        to: (x1, y1),
+        zoom,
+        follow,
+      } => {
+        let follow = if follow { "ON" } else { "OFF" };
+        by_lang!(
+          f,
+          "Vue: ({x0}, {y0}) → ({x1}, {y1}) | Zoom x{zoom} | Suivi: {follow}",
+          "View: ({x0}, {y0}) → ({x1}, {y1}) | Zoom x{zoom} | Follow: {follow}"
+        )
+      }
+      Msg::OverlayLine(overlay) => {
+        let name = Msg::OverlayName(overlay);
+        by_lang!(
+          f,
+          "Calque: {name} (h pour changer)",
+          "Overlay: {name} (h to change)"
+        )
+      }
+      Msg::Automation(on) => write!(f, "Automation: {}", if on { "ON" } else { "OFF" }),
+      Msg::MapSize {
+        width,
+        height,
+        turn,
+      } => by_lang!(
+        f,
+        "Taille de la carte: {width}x{height} | Tour: {turn}",
+        "Map size: {width}x{height} | Turn: {turn}"
+      ),
+      Msg::ZonesExplored {
+        unlocked,
+        total,
+        percentage,
+      } => by_lang!(
+        f,
+        "Zones explorées: {unlocked}/{total} ({percentage:.0}%)",
+        "Zones explored: {unlocked}/{total} ({percentage:.0}%)"
+      ),
+      Msg::AccessibleMinerals(n) => {
+        by_lang!(f, "Minerais accessibles: {n}", "Accessible minerals: {n}")
+      }
+      Msg::AccessibleEnergies(n) => {
+        by_lang!(f, "Énergies accessibles: {n}", "Accessible energy: {n}")
+      }
+      Msg::MainStation(x, y) => by_lang!(
+        f,
+        "Station principale ({x}, {y})",
+        "Main station ({x}, {y})"
+      ),
+      Msg::Outpost(i, x, y) => by_lang!(f, "Avant-poste {i} ({x}, {y})", "Outpost {i} ({x}, {y})"),
+      Msg::NoResources => by_lang!(f, "Aucune ressource", "No resources"),
+      Msg::StationStock(resource, qty) => {
+        let name = Msg::Resource(resource);
+        by_lang!(f, "{name} : {qty} unités", "{name}: {qty} units")
+      }
+      Msg::Paused => by_lang!(f, "Simulation en pause", "Simulation paused"),
+      Msg::Running(speed, ms) => by_lang!(
+        f,
+        "Simulation: {speed} ({ms} ms par tour de base)",
+        "Simulation: {speed} ({ms} ms per base tick)"
+      ),
+      Msg::MappedRegions(zones) => by_lang!(
+        f,
+        "Régions cartographiées: {zones}",
+        "Mapped regions: {zones}"
+      ),
+      Msg::NoBuildSites => by_lang!(f, "Chantiers: aucun", "Build queue: empty"),
+      Msg::BuildSites(sites) => by_lang!(f, "Chantiers: {sites}", "Build queue: {sites}"),
+      Msg::SuggestedShortcut(x, y, saved) => by_lang!(
+        f,
+        "Raccourci suggéré: ({x}, {y}), gain de {saved} pas par trajet",
+        "Suggested shortcut: ({x}, {y}), saves {saved} steps per trip"
+      ),
+      Msg::NoRobotSelected => by_lang!(
+        f,
+        "Aucun robot sélectionné (Tab pour en choisir un)",
+        "No robot selected (Tab to pick one)"
+      ),
+      Msg::RobotHeader {
+        index,
+        x,
+        y,
+        automated,
+      } => match lang() {
+        Lang::Fr => write!(
+          f,
+          "#{index} en ({x}, {y}) | Mode: {}",
+          if automated { "auto" } else { "manuel" }
+        ),
+        Lang::En => write!(
+          f,
+          "#{index} at ({x}, {y}) | Mode: {}",
+          if automated { "auto" } else { "manual" }
+        ),
+      },
+      Msg::TaskLine(task) => {
+        let task = Msg::Task(task);
+        by_lang!(f, "Tâche: {task}", "Task: {task}")
+      }
+      Msg::SpeedKmh(speed) => by_lang!(
+        f,
+        "Vitesse du robot: {speed:.2} km/h",
+        "Robot speed: {speed:.2} km/h"
+      ),
+      Msg::SpeedCells(speed) => by_lang!(
+        f,
+        "Vitesse du robot: {speed:.1} cases par tour",
+        "Robot speed: {speed:.1} cells per tick"
+      ),
+      Msg::Inventory => by_lang!(f, "Inventaire:", "Inventory:"),
+      Msg::Cargo(gauge, load, capacity) => {
+        by_lang!(
+          f,
+          "Soute: {gauge} {load}/{capacity}",
+          "Hold: {gauge} {load}/{capacity}"
+        )
+      }
+      Msg::FleetCargo(list) => by_lang!(f, "Soutes: {list}", "Holds: {list}"),
+      Msg::Telemetry {
+        distance,
+        delivered,
+        idle,
+        blocked,
+      } => by_lang!(
+        f,
+        "Parcouru: {distance} cases | Livré: {delivered} | Inactif: {idle} tours | Bloqué: {blocked} tours",
+        "Travelled: {distance} cells | Delivered: {delivered} | Idle: {idle} ticks | Blocked: {blocked} ticks"
+      ),
+      Msg::BlockedFor(ticks) => by_lang!(
+        f,
+        "Bloqué depuis {ticks} tours, besoin d'aide !",
+        "Blocked for {ticks} ticks, needs help!"
+      ),
+      Msg::RobotsStuck(list) => by_lang!(f, "Robots bloqués: {list}", "Stuck robots: {list}"),
+      Msg::CellDescription(x, y, cell) => {
+        let cell = Msg::CellKind(cell);
+        by_lang!(f, "Case ({x}, {y}) : {cell}", "Cell ({x}, {y}): {cell}")
+      }
+      Msg::ZoneDescription(name, unlocked) => match (lang(), unlocked) {
+        (Lang::Fr, true) => write!(f, "Zone {name} (débloquée)"),
+        (Lang::Fr, false) => write!(f, "Zone {name} (verrouillée)"),
+        (Lang::En, true) => write!(f, "Zone {name} (unlocked)"),
+        (Lang::En, false) => write!(f, "Zone {name} (locked)"),
+      },
+      Msg::Remaining => by_lang!(f, "restants", "left"),
+
+      Msg::InventoryChartTitle(tick) => by_lang!(
+        f,
+        "Inventaire des stations (tour {tick}) — g : retour, e : export CSV",
+        "Station inventory (tick {tick}) — g: back, e: CSV export"
+      ),
+      Msg::ZoneChartTitle => by_lang!(
+        f,
+        "Ressources restantes par zone",
+        "Resources left per zone"
+      ),
+      Msg::DeliveriesTitle(total) => by_lang!(
+        f,
+        "Livraisons par tour (total {total})",
+        "Deliveries per tick (total {total})"
+      ),
+      Msg::UtilisationTitle => by_lang!(f, "Utilisation des robots (%)", "Robot utilisation (%)"),
+
+      Msg::ScienceCollected => by_lang!(
+        f,
+        "Lieu scientifique collecté ! Retourne au labo.",
+        "Science site collected! Heading back to the lab."
+      ),
+      Msg::CellOccupied => by_lang!(
+        f,
+        "Déplacement impossible : un autre robot occupe déjà cette case !",
+        "Cannot move: another robot already occupies this cell!"
+      ),
+      Msg::MoveBlocked => by_lang!(f, "Déplacement impossible !", "Cannot move there!"),
+      Msg::MoveOutOfMap => by_lang!(
+        f,
+        "Déplacement impossible : bord de la carte",
+        "Cannot move: edge of the map"
+      ),
+      Msg::HiddenResource => by_lang!(
+        f,
+        "Déplacement impossible : ressource d'une zone encore verrouillée",
+        "Cannot move: resource in a zone that is still locked"
+      ),
+      Msg::NothingToCollect => by_lang!(f, "Rien à ramasser ici", "Nothing to collect here"),
+      Msg::ResourceLocked => by_lang!(
+        f,
+        "Ressource d'une zone encore verrouillée",
+        "Resource in a zone that is still locked"
+      ),
+      Msg::CargoRefused => by_lang!(
+        f,
+        "La soute de ce robot n'accepte pas cette ressource",
+        "This robot's hold does not take that resource"
+      ),
+      Msg::CargoFull => by_lang!(
+        f,
+        "Soute pleine, retour à la station nécessaire",
+        "Hold full, the robot must unload at a station"
+      ),
+      Msg::ZoneUnlocked(name) => by_lang!(f, "Zone {name} débloquée", "Zone {name} unlocked"),
+      Msg::ScriptError(script, error) => by_lang!(
+        f,
+        "Erreur du script {script} : {error}",
+        "Script {script} failed: {error}"
+      ),
+      Msg::ScriptSingleStep(dx, dy) => by_lang!(
+        f,
+        "move_by({dx}, {dy}) : un seul pas à la fois",
+        "move_by({dx}, {dy}): one step at a time"
+      ),
+      Msg::ScriptBudget(ops) => by_lang!(
+        f,
+        "budget de {ops} opérations par tour dépassé",
+        "exceeded the budget of {ops} operations per tick"
+      ),
+      Msg::ScriptNoAction => by_lang!(
+        f,
+        "decide() doit renvoyer une action",
+        "decide() must return an action"
+      ),
+      Msg::ObstacleDemolished(x, y) => by_lang!(
+        f,
+        "Obstacle démoli en ({x}, {y})",
+        "Obstacle demolished at ({x}, {y})"
+      ),
+      Msg::RoadBuilt(x, y) => by_lang!(
+        f,
+        "Route construite en ({x}, {y})",
+        "Road built at ({x}, {y})"
+      ),
+      Msg::OrderUnreachable(x, y) => by_lang!(
+        f,
+        "Ordre impossible : ({x}, {y}) est inaccessible",
+        "Order dropped: ({x}, {y}) is unreachable"
+      ),
+      Msg::DestinationReached(x, y) => {
+        by_lang!(f, "Destination ({x}, {y}) atteinte", "Reached ({x}, {y})")
+      }
+      Msg::RobotBlocked {
+        index,
+        ticks,
+        dropped,
+      } => match (lang(), dropped) {
+        (Lang::Fr, true) => write!(
+          f,
+          "Robot #{index} bloqué depuis {ticks} tours : ordre abandonné"
+        ),
+        (Lang::Fr, false) => write!(
+          f,
+          "Robot #{index} bloqué depuis {ticks} tours : nouvel itinéraire"
+        ),
+        (Lang::En, true) => write!(f, "Robot #{index} blocked for {ticks} ticks: order dropped"),
+        (Lang::En, false) => write!(f, "Robot #{index} blocked for {ticks} ticks: rerouting"),
+      },
+      Msg::OutpostNotBuildable(x, y) => by_lang!(
+        f,
+        "Impossible de construire un avant-poste en ({x}, {y})",
+        "Cannot build an outpost at ({x}, {y})"
+      ),
+      Msg::OutpostTooExpensive(minerals, energy) => by_lang!(
+        f,
+        "Avant-poste trop cher : {minerals} minerais et {energy} énergies requis",
+        "Outpost too expensive: {minerals} minerals and {energy} energy required"
+      ),
+      Msg::OutpostBuilt(i, x, y) => by_lang!(
+        f,
+        "Avant-poste {i} construit en ({x}, {y})",
+        "Outpost {i} built at ({x}, {y})"
+      ),
+      Msg::StatsExported(path) => by_lang!(
+        f,
+        "Statistiques exportées dans {path}",
+        "Statistics exported to {path}"
+      ),
+      Msg::ExportFailed(error) => {
+        by_lang!(f, "Export impossible : {error}", "Export failed: {error}")
+      }
+      Msg::StocksConsolidated => by_lang!(
+        f,
+        "Stocks des avant-postes transférés à la station principale",
+        "Outpost stocks moved to the main station"
+      ),
+      Msg::StockTransferred(qty, resource, from, to) => {
+        let name = Msg::Resource(resource);
+        by_lang!(
+          f,
+          "{qty} {name} transférés de la station {from} à la station {to}",
+          "{qty} {name} moved from station {from} to station {to}"
+        )
+      }
+      Msg::NoSuchStation(i) => by_lang!(f, "Pas de station {i}", "No station {i}"),
+      Msg::SameStation => by_lang!(
+        f,
+        "Les stations de départ et d'arrivée sont les mêmes",
+        "Source and destination stations are the same"
+      ),
+      Msg::NotEnoughStock(i, available) => by_lang!(
+        f,
+        "La station {i} n'en a que {available}",
+        "Station {i} only has {available}"
+      ),
+      Msg::Quit => by_lang!(f, "Arrêt du programme.", "Shutting down."),
+      Msg::Interrupted => by_lang!(f, "Interruption reçue.", "Interrupted."),
+      Msg::RobotSent(index) => by_lang!(f, "Robot #{index} envoyé", "Robot #{index} sent"),
+      Msg::OrderCancelled(index) => by_lang!(
+        f,
+        "Ordre du robot #{index} annulé",
+        "Robot #{index} order cancelled"
+      ),
+
+      Msg::Error(error) => by_lang!(f, "Erreur : {error}", "Error: {error}"),
+      Msg::MissingArg(arg) => by_lang!(f, "{arg} manquant", "Missing {arg}"),
+      Msg::InvalidArg(arg, word) => by_lang!(f, "{arg} invalide : {word}", "Invalid {arg}: {word}"),
+      Msg::UnknownRobotType(word) => by_lang!(
+        f,
+        "Type de robot inconnu : {word}",
+        "Unknown robot type: {word}"
+      ),
+      Msg::UnknownResource(word) => {
+        by_lang!(f, "Ressource inconnue : {word}", "Unknown resource: {word}")
+      }
+      Msg::EmptyCommand => by_lang!(f, "Commande vide", "Empty command"),
+      Msg::RobotsNumberedFromOne => by_lang!(
+        f,
+        "Les robots sont numérotés à partir de 1",
+        "Robots are numbered from 1"
+      ),
+      Msg::UnknownCommand(name) => {
+        by_lang!(f, "Commande inconnue : {name}", "Unknown command: {name}")
+      }
+      Msg::ExtraArgument(word) => by_lang!(
+        f,
+        "Argument en trop : {word}",
+        "Unexpected argument: {word}"
+      ),
+      Msg::OutOfMap(x, y) => by_lang!(
+        f,
+        "({x}, {y}) est hors de la carte",
+        "({x}, {y}) is outside the map"
+      ),
+      Msg::NoSuchRobot(index) => by_lang!(f, "Pas de robot #{index}", "No robot #{index}"),
+      Msg::RobotSentTo(index, x, y) => by_lang!(
+        f,
+        "Robot #{index} envoyé en ({x}, {y})",
+        "Robot #{index} sent to ({x}, {y})"
+      ),
+      Msg::ResourcesRevealed(true) => by_lang!(f, "Ressources révélées", "Resources revealed"),
+      Msg::ResourcesRevealed(false) => by_lang!(f, "Ressources masquées", "Resources hidden"),
+      Msg::UnknownZone(name) => by_lang!(f, "Zone inconnue : {name}", "Unknown zone: {name}"),
+      Msg::NotPassable(x, y) => by_lang!(
+        f,
+        "({x}, {y}) n'est pas praticable",
+        "({x}, {y}) is not passable"
+      ),
+      Msg::InspectedRobot(index, task) => write!(f, " | Robot #{index} ({task})"),
+      Msg::RobotSpawned(index, x, y) => by_lang!(
+        f,
+        "Robot #{index} créé en ({x}, {y})",
+        "Robot #{index} spawned at ({x}, {y})"
+      ),
+      Msg::ResourcesGiven(qty, resource) => {
+        let name = Msg::Resource(resource);
+        by_lang!(
+          f,
+          "{qty} {name} ajoutés à la station principale",
+          "{qty} {name} added to the main station"
+        )
+      }
+      Msg::TickInterval(ms) => by_lang!(f, "Un tour toutes les {ms} ms", "One tick every {ms} ms"),
+      Msg::MapSeed(seed) => by_lang!(f, "Graine de la carte : {seed}", "Map seed: {seed}"),
+      Msg::SaveFailed(error) => {
+        by_lang!(f, "Sauvegarde impossible : {error}", "Save failed: {error}")
+      }
+      Msg::GameSaved(path) => by_lang!(f, "Partie sauvegardée dans {path}", "Game saved to {path}"),
+      Msg::NotBuildable(x, y) => by_lang!(
+        f,
+        "Rien à construire en ({x}, {y})",
+        "Nothing to build at ({x}, {y})"
+      ),
+      Msg::AlreadyQueued(x, y) => by_lang!(
+        f,
+        "({x}, {y}) est déjà dans la file de construction",
+        "({x}, {y}) is already in the build queue"
+      ),
+      Msg::BuildQueued(x, y, cost, position) => by_lang!(
+        f,
+        "Chantier ({x}, {y}) ajouté en position {position}, coût {cost} minerai",
+        "Site ({x}, {y}) queued at position {position}, costs {cost} mineral"
+      ),
+      Msg::NotQueued(x, y) => by_lang!(
+        f,
+        "({x}, {y}) n'est pas dans la file de construction",
+        "({x}, {y}) is not in the build queue"
+      ),
+      Msg::BuildCancelled(x, y) => {
+        by_lang!(f, "Chantier ({x}, {y}) annulé", "Site ({x}, {y}) cancelled")
+      }
+      Msg::BuildPrioritized(x, y) => by_lang!(
+        f,
+        "Chantier ({x}, {y}) passé en tête de file",
+        "Site ({x}, {y}) moved to the front of the queue"
+      ),
+      Msg::CommandsHelp(names) => by_lang!(f, "Commandes : {names}", "Commands: {names}"),
+
+      Msg::TournamentRuns { runs, threads } => by_lang!(
+        f,
+        "Tournoi : {runs} parties sur {threads} fils",
+        "Tournament: {runs} runs on {threads} threads"
+      ),
+      Msg::TournamentConfig {
+        width,
+        height,
+        ticks,
+      } => by_lang!(
+        f,
+        "Carte {width}x{height}, {ticks} tours",
+        "{width}x{height} map, {ticks} ticks"
+      ),
+      Msg::TournamentColumns => {
+        let (strategy, resources, unlock, idle) = match lang() {
+          Lang::Fr => (
+            "stratégie",
+            "ressources/tour",
+            "tours → carte ouverte",
+            "inactivité %",
+          ),
+          Lang::En => ("strategy", "resources/tick", "ticks → all zones", "idle %"),
+        };
+        write!(
+          f,
+          "{:<3} {strategy:<28} {resources:>17} {unlock:>26} {idle:>15}",
+          "#"
+        )
+      }
+      Msg::UnknownStrategy(name) => {
+        by_lang!(f, "Stratégie inconnue : {name}", "Unknown strategy: {name}")
+      }
+      Msg::BadSeedRange(range) => by_lang!(
+        f,
+        "Plage de graines invalide : {range} (attendu début..fin)",
+        "Invalid seed range: {range} (expected start..end)"
+      ),
+      Msg::BadMapSize(size) => by_lang!(
+        f,
+        "Taille de carte invalide : {size} (attendu LARGEURxHAUTEUR)",
+        "Invalid map size: {size} (expected WIDTHxHEIGHT)"
+      ),
+      Msg::MissingOptionValue(option, expected) => by_lang!(
+        f,
+        "{option} attend {expected}",
+        "{option} expects {expected}"
+      ),
+      Msg::UnknownGlyphSet(name) => by_lang!(
+        f,
+        "Jeu de symboles inconnu : {name}",
+        "Unknown glyph set: {name}"
+      ),
+      Msg::UnknownTheme(name) => by_lang!(f, "Thème inconnu : {name}", "Unknown theme: {name}"),
+      Msg::UnknownLang(name) => by_lang!(f, "Langue inconnue : {name}", "Unknown language: {name}"),
+      Msg::UnknownOption(arg) => by_lang!(f, "Option inconnue : {arg}", "Unknown option: {arg}"),
+      Msg::ScriptOption(arg) => by_lang!(
+        f,
+        "--script attend <type>=<fichier.rhai>, reçu {arg}",
+        "--script expects <type>=<file.rhai>, got {arg}"
+      ),
+
+      Msg::InvariantsBroken(tick, seed) => by_lang!(
+        f,
+        "Invariants enfreints au tour {tick} (graine {seed})",
+        "Invariants broken at tick {tick} (seed {seed})"
+      ),
+      Msg::ResourcesNotConserved(resource, before, after) => by_lang!(
+        f,
+        "{resource} : {before} unités au début du tour, {after} à la fin",
+        "{resource}: {before} units at the start of the tick, {after} at the end"
+      ),
+      Msg::RobotsStacked(a, b, x, y) => by_lang!(
+        f,
+        "Robots #{a} et #{b} sur la même case ({x}, {y}) hors station",
+        "Robots #{a} and #{b} share cell ({x}, {y}) outside a station"
+      ),
+      Msg::RobotOnBlockedCell(robot, x, y) => by_lang!(
+        f,
+        "Robot #{robot} sur une case infranchissable ({x}, {y})",
+        "Robot #{robot} stands on an impassable cell ({x}, {y})"
+      ),
+      Msg::RobotOverCapacity(robot, load, capacity) => by_lang!(
+        f,
+        "Robot #{robot} chargé à {load} pour une soute de {capacity}",
+        "Robot #{robot} holds a load of {load} for a capacity of {capacity}"
+      ),
+      Msg::ZoneCountMismatch(zone, (stored_minerals, stored_energies), (minerals, energies)) => {
+        by_lang!(
+          f,
+          "Zone {zone} : compteurs {stored_minerals} minerais / {stored_energies} énergies, \
+           carte {minerals} / {energies}",
+          "Zone {zone}: counters {stored_minerals} minerals / {stored_energies} energies, \
+           map {minerals} / {energies}"
+        )
+      }
+
+      Msg::MapTooSmall {
+        width,
+        height,
+        min: (min_width, min_height),
+      } => by_lang!(
+        f,
+        "Carte trop petite : {width}x{height} (au moins {min_width}x{min_height})",
+        "Map too small: {width}x{height} (at least {min_width}x{min_height})"
+      ),
+      Msg::TerminalFailed(error) => {
+        by_lang!(f, "Erreur du terminal : {error}", "Terminal error: {error}")
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn key_help_fits_its_columns() {
+    for (key, help) in KEY_HELP_FR.iter().chain(&KEY_HELP_EN) {
+      assert!(key.chars().count() <= KEY_WIDTH, "{key}");
+      assert!(help.chars().count() <= KEY_HELP_WIDTH, "{help}");
+    }
+  }
+}
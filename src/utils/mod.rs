@@ -1,3 +1,12 @@
+pub mod camera;
+pub mod console;
 pub mod display;
+pub mod front_end;
+pub mod keyboard;
+pub mod locale;
+pub mod mouse;
 pub mod noise;
-pub mod keyboard;
\ No newline at end of file
+pub mod overlay;
+pub mod stats_view;
+pub mod terminal;
+pub mod theme;
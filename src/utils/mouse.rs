@@ -0,0 +1,59 @@
+use crate::GameState;
+use crate::utils::display::describe_cell;
+use crate::utils::front_end::FrontEnd;
+use crate::utils::locale::Msg;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+fn click(state: &mut GameState, front: &FrontEnd, x: usize, y: usize) {
+  let mut message = describe_cell(state, x, y, &front.theme);
+  // Avec un robot sélectionné, le clic lui donne l'ordre de s'y rendre
+  if let Some(i) = front.selected_robot {
+    state.robots[i].order = Some((x, y));
+    message.push_str(&format!(" | {}", Msg::RobotSent(i + 1)));
+  }
+  state.last_collect_message = Some(message);
+}
+
+// Glisser avec le bouton gauche fait défiler la vue, un simple clic agit sur la case
+pub fn handle_mouse_event(state: &mut GameState, front: &mut FrontEnd, mouse_event: MouseEvent) {
+  let (column, row) = (mouse_event.column, mouse_event.row);
+  let cell = front.camera.screen_to_cell(column, row, &state.map);
+
+  match mouse_event.kind {
+    MouseEventKind::Down(MouseButton::Left) => {
+      front.camera.drag_from = cell.map(|_| (column, row));
+      front.camera.dragged = false;
+    }
+    MouseEventKind::Drag(MouseButton::Left) => {
+      if let Some((from_column, from_row)) = front.camera.drag_from {
+        let dx = (from_column as isize - column as isize) / 2;
+        let dy = from_row as isize - row as isize;
+        if dx != 0 || dy != 0 {
+          front.camera.follow = false;
+          front.camera.scroll(dx, dy, &state.map);
+          front.camera.drag_from = Some((column, row));
+          front.camera.dragged = true;
+        }
+      }
+    }
+    MouseEventKind::Up(MouseButton::Left) => {
+      let dragged = front.camera.dragged;
+      let pressed = front.camera.drag_from.take().is_some();
+      if pressed
+        && !dragged
+        && let Some((x, y)) = cell
+      {
+        click(state, front, x, y);
+      }
+    }
+    MouseEventKind::Down(MouseButton::Right) => {
+      if cell.is_some()
+        && let Some(i) = front.selected_robot
+        && state.robots[i].order.take().is_some()
+      {
+        state.last_collect_message = Some(Msg::OrderCancelled(i + 1).to_string());
+      }
+    }
+    _ => {}
+  }
+}
@@ -0,0 +1,131 @@
+use crate::GameState;
+use crate::game::construction::approach_cell;
+use crate::game::game_automation::find_path;
+use crate::map::cell::Cell;
+use crate::map::flow::FlowTarget;
+use crate::map::grid::Grid;
+use crate::map::map::Background;
+use crate::robot::robot::RobotTask;
+use crate::utils::theme::Theme;
+
+// Rayon de la fenêtre utilisée pour la densité de ressources
+const DENSITY_RADIUS: usize = 2;
+
+// Calques affichables sous la carte, parcourus avec `h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overlay {
+  #[default]
+  None,
+  Traffic,
+  Resources,
+  Distance,
+  Zones,
+  Paths,
+}
+
+impl Overlay {
+  pub fn next(self) -> Self {
+    match self {
+      Overlay::None => Overlay::Traffic,
+      Overlay::Traffic => Overlay::Resources,
+      Overlay::Resources => Overlay::Distance,
+      Overlay::Distance => Overlay::Zones,
+      Overlay::Zones => Overlay::Paths,
+      Overlay::Paths => Overlay::None,
+    }
+  }
+}
+
+fn heatmap(values: &Grid<Option<u32>>, theme: &Theme) -> Background {
+  let max = values
+    .cells()
+    .iter()
+    .flatten()
+    .copied()
+    .max()
+    .unwrap_or(0)
+    .max(1);
+  values.map(|v| v.map(|v| theme.heat(v as f32 / max as f32)))
+}
+
+fn resource_density(state: &GameState) -> Grid<Option<u32>> {
+  let map = &state.map;
+  let is_resource = |x: usize, y: usize| {
+    matches!(map.grid()[(x, y)], Cell::Mineral | Cell::Energy)
+      && (state.resources_revealed || map.is_resource_accessible(x, y))
+  };
+
+  Grid::from_fn(map.width, map.height, |x, y| {
+    let ys = y.saturating_sub(DENSITY_RADIUS)..=(y + DENSITY_RADIUS).min(map.height - 1);
+    let count = ys
+      .flat_map(|ny| {
+        let xs = x.saturating_sub(DENSITY_RADIUS)..=(x + DENSITY_RADIUS).min(map.width - 1);
+        xs.map(move |nx| (nx, ny))
+      })
+      .filter(|&(nx, ny)| is_resource(nx, ny))
+      .count() as u32;
+    (count > 0).then_some(count)
+  })
+}
+
+fn zones(state: &GameState, theme: &Theme) -> Background {
+  let map = &state.map;
+  let palette = theme.palette();
+  Grid::from_fn(map.width, map.height, |x, y| {
+    let zone = map.get_zone_at(x, y)?;
+    let edge = x == zone.min_x || x == zone.max_x || y == zone.min_y || y == zone.max_y;
+    let (inside, border) = if zone.is_unlocked {
+      palette.zone_unlocked
+    } else {
+      palette.zone_locked
+    };
+    Some(if edge { border } else { inside })
+  })
+}
+
+fn planned_paths(state: &GameState, theme: &Theme) -> Background {
+  let map = &state.map;
+  let colors = &theme.palette().paths;
+  let mut background = Grid::new(map.width, map.height, None);
+
+  for (i, robot) in state.robots.iter().enumerate() {
+    let target = match (robot.order, robot.task) {
+      (Some(order), _) => Some(order),
+      (None, RobotTask::Exploring(x, y) | RobotTask::Collecting(x, y)) => Some((x, y)),
+      (None, RobotTask::Returning(s)) => state.stations.get(s).map(|s| (s.x, s.y)),
+      (None, RobotTask::Building(x, y)) => {
+        approach_cell(robot.x, robot.y, (x, y), map, state.resources_revealed)
+      }
+      _ => None,
+    };
+
+    let path = target
+      .and_then(|(tx, ty)| find_path(robot.x, robot.y, tx, ty, map, state.resources_revealed));
+    for (x, y) in path.into_iter().flatten() {
+      background[(x, y)] = Some(colors[i % colors.len()]);
+    }
+  }
+
+  background
+}
+
+pub fn compute_overlay(state: &GameState, overlay: Overlay, theme: &Theme) -> Option<Background> {
+  match overlay {
+    Overlay::None => None,
+    Overlay::Traffic => {
+      let visits = state.map.traffic.map(|v| (*v > 0).then_some(*v));
+      Some(heatmap(&visits, theme))
+    }
+    Overlay::Resources => Some(heatmap(&resource_density(state), theme)),
+    Overlay::Distance => {
+      // Champ déjà tenu à jour par la simulation, rien à recalculer à chaque image
+      let map = &state.map;
+      let distances = Grid::from_fn(map.width, map.height, |x, y| {
+        map.flow_distance(FlowTarget::Station, x, y)
+      });
+      Some(heatmap(&distances, theme))
+    }
+    Overlay::Zones => Some(zones(state, theme)),
+    Overlay::Paths => Some(planned_paths(state, theme)),
+  }
+}
@@ -0,0 +1,177 @@
+use crate::GameState;
+use crate::game::stats::{RobotActivity, TickSample};
+use crate::robot::robot::ResourceType;
+use crate::utils::locale::Msg;
+use crate::utils::theme::{Theme, tui_color};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::Span;
+use ratatui::widgets::{Axis, BarChart, Block, Borders, Chart, Dataset, GraphType, Sparkline};
+
+// Nombre de tours affichés dans les graphiques
+const WINDOW: usize = 200;
+
+// Courbe nommée : légende, couleur et points (tour, valeur)
+type Line = (String, Color, Vec<(f64, f64)>);
+
+fn series(samples: &[&TickSample], value: impl Fn(&TickSample) -> u32) -> Vec<(f64, f64)> {
+  samples
+    .iter()
+    .map(|s| (s.tick as f64, value(s) as f64))
+    .collect()
+}
+
+fn draw_line_chart(
+  frame: &mut Frame,
+  area: Rect,
+  title: &str,
+  samples: &[&TickSample],
+  lines: &[Line],
+) {
+  let first = samples.first().map_or(0.0, |s| s.tick as f64);
+  let last = samples
+    .last()
+    .map_or(1.0, |s| s.tick as f64)
+    .max(first + 1.0);
+  let top = lines
+    .iter()
+    .flat_map(|(_, _, data)| data.iter().map(|(_, y)| *y))
+    .fold(1.0, f64::max);
+
+  let datasets = lines
+    .iter()
+    .map(|(name, color, data)| {
+      Dataset::default()
+        .name(name.clone())
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(*color))
+        .data(data)
+    })
+    .collect();
+
+  let chart = Chart::new(datasets)
+    .block(
+      Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL),
+    )
+    .x_axis(Axis::default().bounds([first, last]).labels(vec![
+      Span::raw(format!("{first}")),
+      Span::raw(format!("{last}")),
+    ]))
+    .y_axis(
+      Axis::default()
+        .bounds([0.0, top])
+        .labels(vec![Span::raw("0"), Span::raw(format!("{top}"))]),
+    );
+  frame.render_widget(chart, area);
+}
+
+pub fn draw_stats(frame: &mut Frame, state: &GameState, theme: &Theme) {
+  let stats = &state.stats;
+  let palette = theme.palette();
+  let skip = stats.samples.len().saturating_sub(WINDOW);
+  let samples: Vec<&TickSample> = stats.samples.iter().skip(skip).collect();
+
+  let rows = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([
+      Constraint::Percentage(35),
+      Constraint::Percentage(35),
+      Constraint::Percentage(30),
+    ])
+    .split(frame.size());
+  let bottom = Layout::default()
+    .direction(Direction::Horizontal)
+    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+    .split(rows[2]);
+
+  let inventory = vec![
+    (
+      Msg::Resource(&ResourceType::Mineral).to_string(),
+      tui_color(palette.mineral),
+      series(&samples, |s| s.station_inventory[0]),
+    ),
+    (
+      Msg::Resource(&ResourceType::Energy).to_string(),
+      tui_color(palette.energy),
+      series(&samples, |s| s.station_inventory[1]),
+    ),
+    (
+      Msg::Resource(&ResourceType::Science).to_string(),
+      tui_color(palette.science),
+      series(&samples, |s| s.station_inventory[2]),
+    ),
+  ];
+  draw_line_chart(
+    frame,
+    rows[0],
+    &Msg::InventoryChartTitle(stats.tick).to_string(),
+    &samples,
+    &inventory,
+  );
+
+  let zones: Vec<Line> = state
+    .map
+    .zones
+    .iter()
+    .enumerate()
+    .map(|(i, zone)| {
+      (
+        zone.name.clone(),
+        tui_color(palette.series[i % palette.series.len()]),
+        series(&samples, |s| {
+          s.zone_remaining
+            .get(i)
+            .map_or(0, |(minerals, energies)| minerals + energies)
+        }),
+      )
+    })
+    .collect();
+  draw_line_chart(
+    frame,
+    rows[1],
+    &Msg::ZoneChartTitle.to_string(),
+    &samples,
+    &zones,
+  );
+
+  let deliveries: Vec<u64> = samples.iter().map(|s| s.deliveries as u64).collect();
+  let sparkline = Sparkline::default()
+    .block(
+      Block::default()
+        .title(Msg::DeliveriesTitle(stats.total_deliveries).to_string())
+        .borders(Borders::ALL),
+    )
+    .style(Style::default().fg(tui_color(palette.robot)))
+    .data(&deliveries);
+  frame.render_widget(sparkline, bottom[0]);
+
+  // Part (en %) de chaque activité sur la fenêtre affichée
+  let total = |value: fn(&TickSample) -> u32| samples.iter().map(|s| value(s) as u64).sum::<u64>();
+  let all = total(|s| s.moving + s.idle + s.blocked + s.unloading).max(1);
+  let shares = [
+    (RobotActivity::Moving, total(|s| s.moving)),
+    (RobotActivity::Idle, total(|s| s.idle)),
+    (RobotActivity::Blocked, total(|s| s.blocked)),
+    (RobotActivity::Unloading, total(|s| s.unloading)),
+  ]
+  .map(|(activity, count)| (Msg::Activity(activity).to_string(), count * 100 / all));
+  let utilisation: Vec<(&str, u64)> = shares
+    .iter()
+    .map(|(label, share)| (label.as_str(), *share))
+    .collect();
+  let bars = BarChart::default()
+    .block(
+      Block::default()
+        .title(Msg::UtilisationTitle.to_string())
+        .borders(Borders::ALL),
+    )
+    .bar_width(10)
+    .bar_style(Style::default().fg(tui_color(palette.station)))
+    .data(&utilisation[..]);
+  frame.render_widget(bars, bottom[1]);
+}
@@ -0,0 +1,68 @@
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{
+  EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use std::io::{self, stdout};
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Vrai tant qu'un garde tient le terminal : le crochet de panique sait alors qu'il doit le rendre
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+fn restore() {
+  execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen, Show).ok();
+  disable_raw_mode().ok();
+}
+
+// Terminal en mode brut, sur l'écran alternatif, souris capturée et curseur caché le temps
+// de la partie : il retrouve son état d'origine quand le garde disparaît, sur une erreur
+// comme sur une panique
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+  pub fn enter() -> io::Result<Self> {
+    // Le message de panique doit s'afficher sur le terminal rendu, pas sur l'écran du jeu
+    PANIC_HOOK.call_once(|| {
+      let previous = std::panic::take_hook();
+      std::panic::set_hook(Box::new(move |info| {
+        if ACTIVE.swap(false, Ordering::SeqCst) {
+          restore();
+        }
+        previous(info);
+      }));
+    });
+
+    enable_raw_mode()?;
+    // Créé tout de suite, pour que l'échec de la suite rende déjà le mode normal
+    let guard = TerminalGuard;
+    ACTIVE.store(true, Ordering::SeqCst);
+    execute!(stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+    Ok(guard)
+  }
+}
+
+impl Drop for TerminalGuard {
+  fn drop(&mut self) {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+      restore();
+    }
+  }
+}
+
+// SIGINT et SIGTERM demandent un arrêt propre, traité par la boucle de jeu
+pub fn handle_signals() -> Result<(), ctrlc::Error> {
+  ctrlc::set_handler(interrupt)
+}
+
+// En mode brut, Ctrl+C arrive comme une touche et non comme un signal
+pub fn interrupt() {
+  INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+pub fn interrupted() -> bool {
+  INTERRUPTED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,303 @@
+use crate::map::cell::Cell;
+use crate::robot::robot::{ResourceType, RobotType};
+use colored::{Color, ColoredString, Colorize};
+
+type Rgb = (u8, u8, u8);
+
+// Jeu de symboles : emoji, ou ASCII pour les terminaux et polices qui les alignent mal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GlyphSet {
+  #[default]
+  Emoji,
+  Ascii,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorTheme {
+  #[default]
+  Classic,
+  // Palette d'Okabe et Ito, lisible par les daltoniens
+  ColorBlind,
+}
+
+impl GlyphSet {
+  pub fn parse(name: &str) -> Option<Self> {
+    match name {
+      "emoji" => Some(GlyphSet::Emoji),
+      "ascii" => Some(GlyphSet::Ascii),
+      _ => None,
+    }
+  }
+}
+
+impl ColorTheme {
+  pub fn parse(name: &str) -> Option<Self> {
+    match name {
+      "classic" => Some(ColorTheme::Classic),
+      "colorblind" => Some(ColorTheme::ColorBlind),
+      _ => None,
+    }
+  }
+}
+
+pub struct Palette {
+  pub mineral: Color,
+  pub energy: Color,
+  pub science: Color,
+  pub unknown: Color,
+  pub obstacle: Color,
+  pub road: Color,
+  pub robot: Color,
+  pub station: Color,
+  // Fonds : robot sélectionné, robot en détresse, vue sur la mini-carte
+  pub selected: Color,
+  pub help: Color,
+  pub viewport: Color,
+  // Calque des zones : (intérieur, bord) débloquées puis verrouillées
+  pub zone_unlocked: (Rgb, Rgb),
+  pub zone_locked: (Rgb, Rgb),
+  // Dégradé des cartes de chaleur, du plus froid au plus chaud
+  pub heat: [Rgb; 4],
+  // Calque des trajets : une couleur par robot, réutilisées en boucle
+  pub paths: [Rgb; 6],
+  pub series: [Color; 4],
+}
+
+const CLASSIC: Palette = Palette {
+  mineral: Color::Blue,
+  energy: Color::Yellow,
+  science: Color::Magenta,
+  unknown: Color::Red,
+  obstacle: Color::BrightBlack,
+  road: Color::Yellow,
+  robot: Color::Green,
+  station: Color::Cyan,
+  selected: Color::BrightWhite,
+  help: Color::Red,
+  viewport: Color::Blue,
+  zone_unlocked: ((0, 50, 0), (0, 110, 0)),
+  zone_locked: ((60, 0, 0), (130, 0, 0)),
+  heat: [(0, 0, 160), (0, 160, 0), (160, 160, 0), (160, 0, 0)],
+  paths: [
+    (0, 90, 160),
+    (150, 60, 0),
+    (0, 120, 60),
+    (120, 0, 120),
+    (140, 120, 0),
+    (0, 110, 110),
+  ],
+  series: [Color::Cyan, Color::Magenta, Color::Green, Color::Red],
+};
+
+const COLOR_BLIND: Palette = Palette {
+  mineral: Color::TrueColor {
+    r: 0,
+    g: 114,
+    b: 178,
+  },
+  energy: Color::TrueColor {
+    r: 240,
+    g: 228,
+    b: 66,
+  },
+  science: Color::TrueColor {
+    r: 204,
+    g: 121,
+    b: 167,
+  },
+  unknown: Color::TrueColor {
+    r: 213,
+    g: 94,
+    b: 0,
+  },
+  obstacle: Color::BrightBlack,
+  road: Color::TrueColor {
+    r: 230,
+    g: 159,
+    b: 0,
+  },
+  robot: Color::TrueColor {
+    r: 0,
+    g: 158,
+    b: 115,
+  },
+  station: Color::TrueColor {
+    r: 86,
+    g: 180,
+    b: 233,
+  },
+  selected: Color::BrightWhite,
+  help: Color::TrueColor {
+    r: 213,
+    g: 94,
+    b: 0,
+  },
+  viewport: Color::TrueColor {
+    r: 0,
+    g: 114,
+    b: 178,
+  },
+  zone_unlocked: ((0, 40, 90), (0, 90, 160)),
+  zone_locked: ((90, 50, 0), (170, 100, 0)),
+  heat: [(0, 30, 90), (40, 90, 140), (120, 130, 90), (170, 150, 0)],
+  paths: [
+    (0, 90, 140),
+    (170, 75, 0),
+    (0, 120, 90),
+    (150, 90, 125),
+    (160, 120, 0),
+    (60, 130, 170),
+  ],
+  series: [
+    Color::TrueColor {
+      r: 0,
+      g: 114,
+      b: 178,
+    },
+    Color::TrueColor {
+      r: 230,
+      g: 159,
+      b: 0,
+    },
+    Color::TrueColor {
+      r: 0,
+      g: 158,
+      b: 115,
+    },
+    Color::TrueColor {
+      r: 204,
+      g: 121,
+      b: 167,
+    },
+  ],
+};
+
+// Apparence de tous les affichages : symboles et couleurs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Theme {
+  pub glyphs: GlyphSet,
+  pub colors: ColorTheme,
+}
+
+impl Theme {
+  pub fn palette(&self) -> &'static Palette {
+    match self.colors {
+      ColorTheme::Classic => &CLASSIC,
+      ColorTheme::ColorBlind => &COLOR_BLIND,
+    }
+  }
+
+  // Symbole d'une case de la carte, toujours sur deux colonnes
+  pub fn robot(&self, robot_type: RobotType) -> ColoredString {
+    match (self.glyphs, robot_type) {
+      (GlyphSet::Emoji, RobotType::Explorator) => "👽".normal(),
+      (GlyphSet::Emoji, RobotType::Collector) => "🤖".normal(),
+      (GlyphSet::Emoji, RobotType::Builder) => "👷".normal(),
+      (GlyphSet::Ascii, RobotType::Explorator) => "@ ".color(self.palette().robot).bold(),
+      (GlyphSet::Ascii, RobotType::Collector) => "C ".color(self.palette().robot).bold(),
+      (GlyphSet::Ascii, RobotType::Builder) => "B ".color(self.palette().robot).bold(),
+    }
+  }
+
+  pub fn station(&self) -> ColoredString {
+    match self.glyphs {
+      GlyphSet::Emoji => "🏭".normal(),
+      GlyphSet::Ascii => "S ".color(self.palette().station).bold(),
+    }
+  }
+
+  // `visible` : la ressource est connue, sinon elle apparaît comme inconnue
+  pub fn cell(&self, cell: Cell, visible: bool) -> ColoredString {
+    let palette = self.palette();
+    let ascii = self.glyphs == GlyphSet::Ascii;
+    let pick = |emoji: &'static str, text: &'static str| if ascii { text } else { emoji };
+
+    match cell {
+      Cell::Wall | Cell::Obstacle => pick("██", "##").color(palette.obstacle),
+      Cell::Empty => "  ".white(),
+      Cell::Road => pick("░░", "==").color(palette.road),
+      Cell::Science => pick("🧪", "+ ").color(palette.science).bold(),
+      Cell::Mineral | Cell::Energy if !visible => pick("❓", "? ").color(palette.unknown).bold(),
+      Cell::Mineral => pick("💎", "* ").color(palette.mineral).bold(),
+      Cell::Energy => pick("⚡", "$ ").color(palette.energy).bold(),
+    }
+  }
+
+  // Jauge de remplissage sur `width` symboles, arrondie vers le haut pour qu'une soute
+  // entamée ne paraisse jamais vide
+  pub fn gauge(&self, load: u32, capacity: u32, width: u32) -> String {
+    let (full, empty) = match self.glyphs {
+      GlyphSet::Emoji => ("▰", "▱"),
+      GlyphSet::Ascii => ("#", "-"),
+    };
+    let filled = (load * width).div_ceil(capacity.max(1)).min(width);
+    format!(
+      "{}{}",
+      full.repeat(filled as usize),
+      empty.repeat((width - filled) as usize)
+    )
+  }
+
+  // Symbole court d'une ressource dans les panneaux
+  pub fn resource(&self, resource: &ResourceType) -> &'static str {
+    match (self.glyphs, resource) {
+      (GlyphSet::Emoji, ResourceType::Mineral) => "💎",
+      (GlyphSet::Emoji, ResourceType::Energy) => "⚡",
+      (GlyphSet::Emoji, ResourceType::Science) => "🧪",
+      (GlyphSet::Ascii, ResourceType::Mineral) => "*",
+      (GlyphSet::Ascii, ResourceType::Energy) => "$",
+      (GlyphSet::Ascii, ResourceType::Science) => "+",
+    }
+  }
+
+  // Marque d'une zone débloquée dans les rapports texte
+  pub fn check_mark(&self) -> &'static str {
+    match self.glyphs {
+      GlyphSet::Emoji => "✓",
+      GlyphSet::Ascii => "ok",
+    }
+  }
+
+  // Icône décorative en tête de ligne, omise en mode ASCII
+  pub fn icon(&self, emoji: &str) -> String {
+    match self.glyphs {
+      GlyphSet::Emoji => format!("{emoji} "),
+      GlyphSet::Ascii => String::new(),
+    }
+  }
+
+  // Interpolation linéaire dans le dégradé, t dans [0, 1]
+  pub fn heat(&self, t: f32) -> Rgb {
+    let stops = &self.palette().heat;
+    let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let i = (scaled as usize).min(stops.len() - 2);
+    let k = scaled - i as f32;
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * k) as u8;
+    let (from, to) = (stops[i], stops[i + 1]);
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+  }
+}
+
+// Conversion vers les couleurs de ratatui pour l'onglet statistiques
+pub fn tui_color(color: Color) -> ratatui::style::Color {
+  use ratatui::style::Color as Tui;
+  match color {
+    Color::Black => Tui::Black,
+    Color::Red => Tui::Red,
+    Color::Green => Tui::Green,
+    Color::Yellow => Tui::Yellow,
+    Color::Blue => Tui::Blue,
+    Color::Magenta => Tui::Magenta,
+    Color::Cyan => Tui::Cyan,
+    Color::White => Tui::Gray,
+    Color::BrightBlack => Tui::DarkGray,
+    Color::BrightRed => Tui::LightRed,
+    Color::BrightGreen => Tui::LightGreen,
+    Color::BrightYellow => Tui::LightYellow,
+    Color::BrightBlue => Tui::LightBlue,
+    Color::BrightMagenta => Tui::LightMagenta,
+    Color::BrightCyan => Tui::LightCyan,
+    Color::BrightWhite => Tui::White,
+    Color::TrueColor { r, g, b } => Tui::Rgb(r, g, b),
+  }
+}
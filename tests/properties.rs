@@ -0,0 +1,164 @@
+// Propriétés vérifiées sur des cartes et des parties tirées au hasard : cargo test --test properties
+use ereea::game::command::Command;
+use ereea::game::events::SimEvent;
+use ereea::game::game_automation::{distance_field, find_path, is_passable};
+use ereea::game::invariants::resource_totals;
+use ereea::map::cell::Cell;
+use ereea::map::grid::Grid;
+use ereea::robot::robot::{ResourceType, RobotType};
+use ereea::{Config, GameError, Map, Simulation};
+use proptest::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn cell() -> impl Strategy<Value = Cell> {
+  prop_oneof![
+    6 => Just(Cell::Empty),
+    2 => Just(Cell::Obstacle),
+    1 => Just(Cell::Road),
+    1 => Just(Cell::Mineral),
+    1 => Just(Cell::Energy),
+    1 => Just(Cell::Science),
+  ]
+}
+
+fn grid() -> impl Strategy<Value = Grid<Cell>> {
+  (3usize..16, 3usize..12).prop_flat_map(|(width, height)| {
+    proptest::collection::vec(cell(), width * height)
+      .prop_map(move |cells| Grid::from_fn(width, height, |x, y| cells[y * width + x]))
+  })
+}
+
+proptest! {
+  #[test]
+  fn paths_are_valid_and_shortest(
+    grid in grid(),
+    start in (0usize..16, 0usize..12),
+    target in (0usize..16, 0usize..12),
+    revealed in any::<bool>(),
+  ) {
+    let map = Map::from_grid(grid).unwrap();
+    let start = (start.0 % map.width, start.1 % map.height);
+    let target = (target.0 % map.width, target.1 % map.height);
+    prop_assume!(is_passable(&map, start.0, start.1, revealed));
+    prop_assume!(is_passable(&map, target.0, target.1, revealed));
+
+    let dist = distance_field(&map, &[start], revealed);
+    match find_path(start.0, start.1, target.0, target.1, &map, revealed) {
+      Some(path) => {
+        prop_assert_eq!(path.first(), Some(&start));
+        prop_assert_eq!(path.last(), Some(&target));
+        for pair in path.windows(2) {
+          let ((ax, ay), (bx, by)) = (pair[0], pair[1]);
+          prop_assert_eq!(ax.abs_diff(bx) + ay.abs_diff(by), 1);
+          prop_assert!(is_passable(&map, bx, by, revealed));
+        }
+        prop_assert_eq!(Some(path.len() as u32 - 1), dist[target]);
+      }
+      None => prop_assert_eq!(dist[target], None),
+    }
+  }
+}
+
+proptest! {
+  #![proptest_config(ProptestConfig::with_cases(24))]
+
+  #[test]
+  fn resources_are_conserved(
+    seed in any::<u32>(),
+    width in 12usize..40,
+    height in 8usize..20,
+    reveal_at in proptest::option::of(0u32..150),
+  ) {
+    let config = Config { width, height, seed, ..Config::default() };
+    let mut sim = Simulation::new(&config).unwrap();
+    let initial = resource_totals(&sim.state);
+    for tick in 0..150 {
+      if reveal_at == Some(tick) {
+        sim.apply(Command::Reveal).unwrap();
+      }
+      sim.step();
+      prop_assert_eq!(resource_totals(&sim.state), initial, "tour {}", tick);
+    }
+  }
+
+  #[test]
+  fn the_simulation_keeps_its_invariants(
+    seed in any::<u32>(),
+    width in 0usize..48,
+    height in 0usize..24,
+    automation in any::<bool>(),
+  ) {
+    let config = Config { width, height, seed, ..Config::default() };
+    let mut sim = match Simulation::new(&config) {
+      Ok(sim) => sim,
+      Err(GameError::MapTooSmall { .. }) => return Ok(()),
+      Err(e) => return Err(TestCaseError::fail(e.to_string())),
+    };
+    sim.automation = automation;
+    sim.check_invariants = true;
+    for _ in 0..100 {
+      sim.step();
+      sim.events();
+    }
+  }
+}
+
+// Déroulé d'une partie : à chaque tour, les événements, les robots et les stocks des stations
+type Trace = Vec<(
+  Vec<SimEvent>,
+  Vec<(usize, usize, HashMap<ResourceType, u32>)>,
+  Vec<HashMap<ResourceType, u32>>,
+  Option<String>,
+)>;
+
+fn trace_on(threads: usize, seed: u32) -> Trace {
+  let pool = rayon::ThreadPoolBuilder::new()
+    .num_threads(threads)
+    .build()
+    .unwrap();
+  pool.install(|| {
+    let config = Config {
+      seed,
+      ..Config::default()
+    };
+    let mut sim = Simulation::new(&config).unwrap();
+    // Un script en plus des stratégies intégrées, pour couvrir leurs deux chemins
+    sim
+      .load_scripts(&[(
+        RobotType::Collector,
+        PathBuf::from("scripts/collector.rhai"),
+      )])
+      .unwrap();
+    (0..200)
+      .map(|_| {
+        sim.step();
+        (
+          sim.events(),
+          sim
+            .state
+            .robots
+            .iter()
+            .map(|r| (r.x, r.y, r.inventory.clone()))
+            .collect(),
+          sim
+            .state
+            .stations
+            .iter()
+            .map(|s| s.inventory.clone())
+            .collect(),
+          sim.state.last_collect_message.clone(),
+        )
+      })
+      .collect()
+  })
+}
+
+proptest! {
+  #![proptest_config(ProptestConfig::with_cases(6))]
+
+  #[test]
+  fn parallel_planning_matches_a_single_thread(seed in any::<u32>()) {
+    prop_assert_eq!(trace_on(1, seed), trace_on(4, seed));
+  }
+}